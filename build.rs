@@ -0,0 +1,93 @@
+//! Generates a test for every entry of `answers.toml`, included by `tests/answers.rs`,
+//! and the versions of the solvers, included by `src/cache.rs`.
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    hash::Hasher,
+    path::{Path, PathBuf},
+};
+
+#[allow(dead_code)]
+#[path = "src/answers.rs"]
+mod answers;
+#[allow(dead_code)]
+#[path = "src/hash.rs"]
+mod hash;
+
+use answers::Manifest;
+use hash::Fnv;
+
+fn main() {
+    println!("cargo:rerun-if-changed=answers.toml");
+    println!("cargo:rerun-if-changed=src");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    generate_answer_tests(&out_dir);
+    generate_versions(&out_dir);
+}
+
+fn generate_answer_tests(out_dir: &Path) {
+    let manifest = fs::read_to_string("answers.toml").expect("answers.toml not found!");
+    let manifest = Manifest::parse(&manifest).expect("Could not parse answers.toml");
+
+    let mut tests = String::new();
+    for answer in &manifest.answer {
+        let input_name = Path::new(&answer.input)
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+
+        if let Some(reason) = &answer.ignore {
+            writeln!(tests, "#[ignore = {:?}]", reason).unwrap();
+        }
+        writeln!(
+            tests,
+            "#[test]\nfn {}_part{}() {{\n    check({}, {}, {:?}, {:?});\n}}\n",
+            input_name, answer.part, answer.day, answer.part, answer.input, answer.answer
+        )
+        .unwrap();
+    }
+
+    fs::write(out_dir.join("answers.rs"), tests).unwrap();
+}
+
+/// the version of a day is the hash of its module and of every module which is not a day,
+/// so a cached answer is not used anymore once the code which produced it changed
+fn generate_versions(out_dir: &Path) {
+    let mut sources = fs::read_dir("src")
+        .expect("src not found!")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+        .collect::<Vec<PathBuf>>();
+    sources.sort();
+
+    let day = |path: &Path| -> Option<u8> {
+        path.file_stem()?
+            .to_str()?
+            .strip_prefix("day")?
+            .parse()
+            .ok()
+    };
+
+    let mut shared = Fnv::default();
+    for path in &sources {
+        if day(path).is_none() && !path.ends_with("main.rs") {
+            shared.write(&fs::read(path).unwrap());
+        }
+    }
+
+    let mut versions = String::from("pub const VERSIONS: &[(u8, &str)] = &[\n");
+    for path in &sources {
+        if let Some(day) = day(path) {
+            let mut hasher = shared;
+            hasher.write(&fs::read(path).unwrap());
+            writeln!(versions, "    ({}, \"{:016x}\"),", day, hasher.finish()).unwrap();
+        }
+    }
+    versions.push_str("];\n");
+
+    fs::write(out_dir.join("versions.rs"), versions).unwrap();
+}
@@ -0,0 +1,38 @@
+//! The manifest of known answers, `answers.toml`.
+//!
+//! This module only depends on `serde` and `toml`, `build.rs` includes it to generate the tests.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct Manifest {
+    #[serde(default)]
+    pub answer: Vec<Answer>,
+}
+
+/// The expected answer of a part of a day for an input file.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    /// path of the input, relative to the root of the repository
+    pub input: String,
+    pub answer: String,
+    /// reason to skip the generated test
+    pub ignore: Option<String>,
+}
+
+impl Manifest {
+    pub fn parse(manifest: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(manifest)
+    }
+
+    /// returns the known answer of `part` of `day` for the input file
+    pub fn expected(&self, day: u8, part: u8, input: &Path) -> Option<&Answer> {
+        self.answer.iter().find(|answer| {
+            answer.day == day && answer.part == part && Path::new(&answer.input) == input
+        })
+    }
+}
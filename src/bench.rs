@@ -0,0 +1,179 @@
+//! Repeated timing of the parse, part 1 and part 2 phases of a solver.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{params::Params, ParseError, Solver};
+
+/// Timing statistics of a single phase over all runs, in nanoseconds
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PhaseStats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Benchmark {
+    pub day: u8,
+    /// size of the generated input, none for an input file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<usize>,
+    pub runs: usize,
+    pub parse: PhaseStats,
+    pub part1: PhaseStats,
+    pub part2: PhaseStats,
+}
+
+impl PhaseStats {
+    /// computes the statistics of the measured durations, `durations` can't be empty
+    pub fn new(durations: &[Duration]) -> Self {
+        let mut nanos = durations
+            .iter()
+            .map(|d| d.as_nanos() as u64)
+            .collect::<Vec<u64>>();
+        nanos.sort_unstable();
+
+        let median_ns = match nanos.len() % 2 {
+            0 => (nanos[nanos.len() / 2 - 1] + nanos[nanos.len() / 2]) / 2,
+            _ => nanos[nanos.len() / 2],
+        };
+
+        // nearest rank percentile
+        let p95_rank = (nanos.len() * 95).div_ceil(100);
+
+        PhaseStats {
+            min_ns: nanos[0],
+            median_ns,
+            p95_ns: nanos[p95_rank - 1],
+        }
+    }
+}
+
+impl Benchmark {
+    /// returns the median time of parsing and solving both parts
+    pub fn median_ns(&self) -> u64 {
+        self.parse.median_ns + self.part1.median_ns + self.part2.median_ns
+    }
+}
+
+/// returns a bar chart of the median time against the size of the generated inputs,
+/// the longest bar is `width` wide
+pub fn plot(benchmarks: &[Benchmark], width: usize) -> String {
+    let longest = benchmarks
+        .iter()
+        .map(Benchmark::median_ns)
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    benchmarks
+        .iter()
+        .map(|benchmark| {
+            let bar = (benchmark.median_ns() as u128 * width as u128).div_ceil(longest as u128);
+            format!(
+                "{:>10}  {:>12}  {}\n",
+                benchmark.size.unwrap_or_default(),
+                format!("{:.2?}", Duration::from_nanos(benchmark.median_ns())),
+                "#".repeat(bar as usize)
+            )
+        })
+        .collect()
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let now = Instant::now();
+    let result = black_box(f());
+    (result, now.elapsed())
+}
+
+/// parses and solves the input `runs` times, timing every phase separately
+pub fn bench(
+    solver: &dyn Solver,
+    input: &str,
+    params: &Params,
+    runs: usize,
+) -> Result<Benchmark, ParseError> {
+    assert!(runs > 0, "At least 1 run is needed to benchmark");
+
+    let mut parse = vec![];
+    let mut part1 = vec![];
+    let mut part2 = vec![];
+
+    for _ in 0..runs {
+        let (parsed, elapsed) = timed(|| solver.parse(input));
+        let parsed = parsed?;
+        parse.push(elapsed);
+
+        part1.push(timed(|| solver.solve(parsed.as_ref(), 1, params)).1);
+        part2.push(timed(|| solver.solve(parsed.as_ref(), 2, params)).1);
+    }
+
+    Ok(Benchmark {
+        day: solver.day(),
+        size: None,
+        runs,
+        parse: PhaseStats::new(&parse),
+        part1: PhaseStats::new(&part1),
+        part2: PhaseStats::new(&part2),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_statistics() {
+        let durations = (1..=20)
+            .map(Duration::from_nanos)
+            .collect::<Vec<Duration>>();
+
+        assert_eq!(
+            PhaseStats::new(&durations),
+            PhaseStats {
+                min_ns: 1,
+                median_ns: 10,
+                p95_ns: 19,
+            }
+        );
+    }
+
+    #[test]
+    fn plots_time_against_size() {
+        let stats = |nanos| PhaseStats {
+            min_ns: nanos,
+            median_ns: nanos,
+            p95_ns: nanos,
+        };
+        let benchmark = |size, nanos| Benchmark {
+            day: 9,
+            size: Some(size),
+            runs: 1,
+            parse: stats(nanos),
+            part1: stats(nanos),
+            part2: stats(nanos),
+        };
+
+        assert_eq!(
+            plot(&[benchmark(10, 1_000), benchmark(1000, 100_000)], 20),
+            "        10        3.00µs  #\n      1000      300.00µs  ####################\n"
+        );
+    }
+
+    #[test]
+    fn computes_statistics_of_single_run() {
+        assert_eq!(
+            PhaseStats::new(&[Duration::from_nanos(7)]),
+            PhaseStats {
+                min_ns: 7,
+                median_ns: 7,
+                p95_ns: 7,
+            }
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Memoized answers, keyed by the input, the version of the solver and its parameters.
+//!
+//! Every answer is a JSON file in `.aoc-cache`, named after its day, part and key.
+
+use std::{fs, hash::Hasher, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{hash::Fnv, ledger, params::Params};
+
+pub const CACHE_DIR: &str = ".aoc-cache";
+
+mod versions {
+    include!(concat!(env!("OUT_DIR"), "/versions.rs"));
+}
+
+/// A memoized answer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub key: String,
+    pub answer: String,
+    /// time spent parsing and solving when the answer was computed
+    pub elapsed_ms: f64,
+    /// seconds since the unix epoch
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+/// returns the version of the solver of a day, it changes whenever its code changes
+pub fn version(day: u8) -> &'static str {
+    versions::VERSIONS
+        .iter()
+        .find(|(version_day, _)| *version_day == day)
+        .map_or("unknown", |(_, version)| version)
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Cache { dir: dir.into() }
+    }
+
+    /// returns the key of the answers of a day for the input and parameters
+    pub fn key(day: u8, input: &str, params: &Params) -> String {
+        let mut hasher = Fnv::default();
+        hasher.write(input.as_bytes());
+        hasher.write(version(day).as_bytes());
+        for (name, value) in params.iter() {
+            hasher.write(format!("\n{}={}", name, value).as_bytes());
+        }
+
+        format!("{:016x}", hasher.finish())
+    }
+
+    fn path(&self, day: u8, part: u8, key: &str) -> PathBuf {
+        self.dir
+            .join(format!("day{}-part{}-{}.json", day, part, key))
+    }
+
+    /// returns the memoized answer, `None` if there is none or it can't be read
+    pub fn get(&self, day: u8, part: u8, key: &str) -> Option<Entry> {
+        let entry = fs::read_to_string(self.path(day, part, key)).ok()?;
+        serde_json::from_str(&entry).ok()
+    }
+
+    pub fn insert(
+        &self,
+        day: u8,
+        part: u8,
+        key: &str,
+        answer: &str,
+        elapsed_ms: f64,
+    ) -> io::Result<()> {
+        let entry = Entry {
+            day,
+            part,
+            key: key.to_string(),
+            answer: answer.to_string(),
+            elapsed_ms,
+            timestamp: ledger::now(),
+        };
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            self.path(day, part, key),
+            serde_json::to_string_pretty(&entry)?,
+        )
+    }
+
+    /// returns every memoized answer, sorted by day and part, newest first
+    pub fn entries(&self) -> io::Result<Vec<Entry>> {
+        let mut entries = self
+            .files()?
+            .iter()
+            .filter_map(|path| serde_json::from_str::<Entry>(&fs::read_to_string(path).ok()?).ok())
+            .collect::<Vec<Entry>>();
+        entries.sort_by(|a, b| {
+            (a.day, a.part)
+                .cmp(&(b.day, b.part))
+                .then(b.timestamp.cmp(&a.timestamp))
+        });
+
+        Ok(entries)
+    }
+
+    /// removes the memoized answers of a day, or of every day, returns how many were removed
+    pub fn clear(&self, day: Option<u8>) -> io::Result<usize> {
+        let prefix = day.map(|day| format!("day{}-", day));
+
+        let mut removed = 0;
+        for path in self.files()? {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if prefix
+                .as_ref()
+                .is_none_or(|prefix| name.starts_with(prefix))
+            {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// returns the files of the cache, none if the directory does not exist yet
+    fn files(&self) -> io::Result<Vec<PathBuf>> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => return Err(error),
+        };
+
+        let mut files = vec![];
+        for entry in entries {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == "json")
+            {
+                files.push(path);
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Parameter;
+
+    const ROPE: &[Parameter] = &[Parameter {
+        name: "rope_size",
+        default: 2,
+        min: 1,
+        max: 1000,
+        description: "knots of the rope",
+    }];
+
+    #[test]
+    fn keys_depend_on_input_and_parameters() {
+        let params = Params::new(9, ROPE);
+        let key = Cache::key(9, "R 4\n", &params);
+
+        assert_eq!(key, Cache::key(9, "R 4\n", &params));
+        assert_ne!(key, Cache::key(9, "R 5\n", &params));
+        // another day has another version
+        assert_ne!(key, Cache::key(6, "R 4\n", &params));
+
+        let mut params = params;
+        params.set("rope_size", 10).unwrap();
+        assert_ne!(key, Cache::key(9, "R 4\n", &params));
+    }
+
+    #[test]
+    fn stores_lists_and_clears_answers() {
+        let dir = std::env::temp_dir().join(format!("aoc-cache-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+
+        assert_eq!(cache.entries().unwrap(), vec![]);
+        cache.insert(9, 2, "abc", "36", 1.5).unwrap();
+        cache.insert(9, 1, "abc", "13", 1.5).unwrap();
+        cache.insert(6, 1, "def", "7", 0.1).unwrap();
+
+        assert_eq!(cache.get(9, 2, "abc").unwrap().answer, "36");
+        assert_eq!(cache.get(9, 2, "def"), None);
+        assert_eq!(
+            cache
+                .entries()
+                .unwrap()
+                .iter()
+                .map(|entry| (entry.day, entry.part))
+                .collect::<Vec<_>>(),
+            [(6, 1), (9, 1), (9, 2)]
+        );
+
+        assert_eq!(cache.clear(Some(9)).unwrap(), 2);
+        assert_eq!(cache.entries().unwrap().len(), 1);
+        assert_eq!(cache.clear(None).unwrap(), 1);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
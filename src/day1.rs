@@ -1,25 +1,259 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
+use std::{cmp::Reverse, collections::BinaryHeap, fmt};
+
+use serde::Serialize;
+
+use crate::{
+    error::{self, Line},
+    params::{Parameter, Params},
+    ParseError, Solution,
 };
 
-fn main() {
-    let file = File::open("./data/day1.input").expect("file not found!");
-    let buf_reader = io::BufReader::new(file);
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+
+    /// the elves with the calories of the items they carry
+    type Input = Vec<Elf>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    const PARAMETERS: &'static [Parameter] = &[Parameter {
+        name: "top_elves",
+        default: 3,
+        min: 1,
+        max: 1_000_000,
+        description: "elves carrying the most calories summed in part 2",
+    }];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut elves: Vec<Elf> = Vec::new();
+        let mut items: Vec<u32> = Vec::new();
 
-    let mut calories: Vec<u32> = Vec::new();
+        // the elves are separated by blank lines, the last one may not be followed by one
+        for line in error::lines(Self::DAY, input).map(Some).chain([None]) {
+            match line.map(|line| (line, line.text.trim())) {
+                Some((line, calories)) if !calories.is_empty() => {
+                    items.push(line.parse(calories, "a calorie count or a blank line")?)
+                }
+                _ if !items.is_empty() => elves.push(Elf {
+                    index: elves.len() + 1,
+                    items: std::mem::take(&mut items),
+                }),
+                _ => {}
+            }
+        }
 
-    let subtotal = 0;
-    buf_reader.lines().flatten().fold(subtotal, |subtotal, line| {
-        if let Ok(calorie) = line.parse::<u32>() {
-            subtotal + calorie
-        } else {
-            calories.push(subtotal);
-            0
+        if elves.is_empty() {
+            return Err(ParseError::new(
+                Self::DAY,
+                input,
+                "at least one elf carrying calories",
+            ));
         }
-    });
 
-    println!("Part 1: {}", calories.iter().max().unwrap());
-    calories.sort_by(|a,b| b.cmp(a));
-    println!("Part 2: {:?}", calories.iter().take(3).sum::<u32>());
+        Ok(elves)
+    }
+
+    fn part1(elves: &Self::Input, _params: &Params) -> Self::Answer1 {
+        elves.iter().map(Elf::total).max().unwrap()
+    }
+
+    fn part2(elves: &Self::Input, params: &Params) -> Self::Answer2 {
+        elves
+            .iter()
+            .map(Elf::total)
+            .top_k(params.get("top_elves"))
+            .iter()
+            .sum()
+    }
+}
+
+/// An elf with the calories of every item it carries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elf {
+    /// 1-based position of the elf in the input
+    pub index: usize,
+    pub items: Vec<u32>,
+}
+
+impl Elf {
+    pub fn total(&self) -> u32 {
+        self.items.iter().sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.total() as f64 / self.items.len() as f64
+    }
+
+    pub fn median(&self) -> f64 {
+        let mut items = self.items.clone();
+        items.sort_unstable();
+
+        match items.len() % 2 {
+            0 => (items[items.len() / 2 - 1] + items[items.len() / 2]) as f64 / 2.0,
+            _ => items[items.len() / 2] as f64,
+        }
+    }
+}
+
+/// The totals of the elves of an input read line by line, which is never held in memory.
+///
+/// Lines which are not calorie counts end the iteration with their error.
+pub struct Totals<I> {
+    lines: I,
+    /// number of the last line read
+    number: usize,
+    failed: bool,
+}
+
+/// returns the calories carried by every elf of the lines, in the order of the input
+pub fn totals<I>(lines: I) -> Totals<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    Totals {
+        lines: lines.into_iter(),
+        number: 0,
+        failed: false,
+    }
+}
+
+impl<I> Iterator for Totals<I>
+where
+    I: Iterator,
+    I::Item: AsRef<str>,
+{
+    type Item = Result<u32, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let mut total = None;
+        for text in self.lines.by_ref() {
+            self.number += 1;
+            let line = Line {
+                day: Day1::DAY,
+                number: self.number,
+                text: text.as_ref(),
+            };
+            let calories = line.text.trim();
+
+            if calories.is_empty() {
+                match total {
+                    Some(total) => return Some(Ok(total)),
+                    None => continue,
+                }
+            }
+            match line.parse::<u32>(calories, "a calorie count or a blank line") {
+                Ok(calories) => total = Some(total.unwrap_or(0) + calories),
+                Err(error) => {
+                    self.failed = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+
+        total.map(Ok)
+    }
+}
+
+/// Keeps the largest items of an iterator in a min-heap of at most `k` items.
+pub trait TopK: Iterator {
+    /// returns the `k` largest items, largest first
+    fn top_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        // grows with the items rather than reserving k, which may be far more than there are
+        let mut heap = BinaryHeap::new();
+        for item in self {
+            heap.push(Reverse(item));
+            // the smallest of the k + 1 items can not be among the largest
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+}
+
+impl<I: Iterator> TopK for I {}
+
+/// The statistics of an elf, ranked by its total.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct ElfStats {
+    /// elves with the same total share their rank
+    pub rank: usize,
+    pub elf: usize,
+    pub total: u32,
+    pub items: usize,
+    pub mean: f64,
+    pub median: f64,
+}
+
+/// The elves ranked by the calories they carry.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Report {
+    pub elves: Vec<ElfStats>,
+    /// calories carried by the top elves of the report together
+    pub top_total: u32,
+}
+
+/// returns the statistics of the `top` elves carrying the most calories, most calories first
+pub fn report(elves: &[Elf], top: usize) -> Report {
+    let mut ranked = elves.iter().collect::<Vec<&Elf>>();
+    ranked.sort_by(|a, b| b.total().cmp(&a.total()).then(a.index.cmp(&b.index)));
+
+    let mut stats: Vec<ElfStats> = Vec::new();
+    for (position, elf) in ranked.into_iter().take(top).enumerate() {
+        let rank = match stats.last() {
+            Some(previous) if previous.total == elf.total() => previous.rank,
+            _ => position + 1,
+        };
+        stats.push(ElfStats {
+            rank,
+            elf: elf.index,
+            total: elf.total(),
+            items: elf.items.len(),
+            mean: elf.mean(),
+            median: elf.median(),
+        });
+    }
+
+    Report {
+        top_total: stats.iter().map(|stats| stats.total).sum(),
+        elves: stats,
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>4}  {:>5}  {:>8}  {:>5}  {:>10}  {:>10}",
+            "rank", "elf", "total", "items", "mean", "median"
+        )?;
+        for elf in &self.elves {
+            writeln!(
+                f,
+                "{:>4}  {:>5}  {:>8}  {:>5}  {:>10.1}  {:>10.1}",
+                elf.rank, elf.elf, elf.total, elf.items, elf.mean, elf.median
+            )?;
+        }
+        writeln!(
+            f,
+            "the top {} elves carry {} calories",
+            self.elves.len(),
+            self.top_total
+        )
+    }
 }
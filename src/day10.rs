@@ -1,76 +1,154 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
+use std::collections::VecDeque;
+
+use crate::{
+    error,
+    params::Params,
+    viz::{Render, Simulation},
+    ParseError, Solution,
 };
 
-enum Operation {
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<Operation>;
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        error::lines(Self::DAY, input)
+            .map(
+                |line| match line.text.split(' ').collect::<Vec<&str>>()[..] {
+                    ["noop"] => Ok(Operation::Noop),
+                    ["addx", amount] => Ok(Operation::Add(line.parse(amount, "an amount to add")?)),
+                    _ => Err(line.error(line.text, "an instruction `noop` or `addx V`")),
+                },
+            )
+            .collect()
+    }
+
+    fn part1(operations: &Self::Input, _params: &Params) -> Self::Answer1 {
+        let (result_cycles, _) = execute(operations);
+
+        result_cycles
+    }
+
+    fn part2(operations: &Self::Input, _params: &Params) -> Self::Answer2 {
+        let (_, screen) = execute(operations);
+
+        screen
+            .chunks(40)
+            .map(|chunk| chunk.join(""))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Operation {
     Add(i32),
     Noop,
 }
 
-fn main() {
-    let file = File::open("./data/day10.input").expect("file not found!");
-    let operations = io::BufReader::new(file)
-        .lines()
-        .flatten()
-        .collect::<Vec<String>>()
-        .iter()
-        .map(|s| {
-            let mut input = s.split(' ');
-            input.next();
-
-            if let Some(amount) = input.next() {
-                return Operation::Add(amount.parse().unwrap());
-            };
-
-            Operation::Noop
-        })
-        .collect::<Vec<Operation>>();
-
-    let mut cycle: u32 = 1;
-    let mut register: i32 = 1;
-    let mut result_cycles: i32 = 0;
-    let mut screen: Vec<&str> = vec![];
-
-    let mut update_final_result = |cycle: u32, register: i32| {
-        if (cycle == 20 || (cycle as i32 - 20) as i32 % 40 == 0) && cycle < 221 {
-            result_cycles += cycle as i32 * register;
-        };
-    };
-
-    let mut draw_on_screen = |register: i32, cycle: u32| {
-        let start = (cycle - (cycle % 40))/ 40;
-        let position = (start as i32 * 40) + register;
-        match cycle - 1 {
-            a if a >= position as u32 - 1 && a <= position as u32 + 1 => screen.push("#"),
-            _ => screen.push("."),
+/// runs the program, returning the sum of the signal strengths and the pixels drawn on the CRT
+fn execute(operations: &[Operation]) -> (i32, Vec<&'static str>) {
+    let mut crt = Crt::new(operations);
+    while crt.step() {}
+
+    (crt.signal_strength, crt.screen)
+}
+
+/// The CPU running a program one cycle at a time, while the CRT draws a pixel every cycle.
+pub struct Crt {
+    /// the operations left to run, the first one is in progress
+    operations: VecDeque<Operation>,
+    /// cycles spent on the operation in progress
+    progress: u32,
+    /// cycles which are done
+    cycle: u32,
+    register: i32,
+    signal_strength: i32,
+    screen: Vec<&'static str>,
+}
+
+impl Crt {
+    pub fn new(operations: &[Operation]) -> Self {
+        Crt {
+            operations: operations.iter().copied().collect(),
+            progress: 0,
+            cycle: 0,
+            register: 1,
+            signal_strength: 0,
+            screen: vec![],
         }
-    };
+    }
+}
+
+impl Simulation for Crt {
+    /// runs a single cycle
+    fn step(&mut self) -> bool {
+        let Some(operation) = self.operations.front() else {
+            return false;
+        };
 
-    operations.iter().for_each(|operation| {
-        draw_on_screen(register, cycle);
         // Cycle is running
-        if let Operation::Add(amount) = operation {
-            // Cycle stops
-            cycle += 1;
-            // Cycle is running
-            draw_on_screen(register, cycle);
-            update_final_result(cycle, register);
-
-            // Add during the second cycle
-            register += amount;
+        self.cycle += 1;
+        // the sprite is three pixels wide, around the register
+        let column = (self.cycle - 1) % 40;
+        self.screen
+            .push(match (column as i32 - self.register).abs() <= 1 {
+                true => "#",
+                false => ".",
+            });
+        if self.cycle % 40 == 20 && self.cycle <= 220 {
+            self.signal_strength += self.cycle as i32 * self.register;
         }
 
-        // Cycle stops
-        cycle += 1;
+        // Cycle stops, add during the second cycle
+        match operation {
+            Operation::Add(amount) if self.progress == 1 => {
+                self.register += amount;
+                self.operations.pop_front();
+                self.progress = 0;
+            }
+            Operation::Add(_) => self.progress += 1,
+            Operation::Noop => {
+                self.operations.pop_front();
+            }
+        }
 
-        // Cycle is running
-        update_final_result(cycle, register);
-    });
+        true
+    }
+}
+
+impl Render for Crt {
+    /// draws the pixels drawn so far with the beam as `@`, and the sprite below the screen
+    fn render(&self) -> String {
+        let pixels = self.screen.len().max(240).div_ceil(40) * 40;
+        let mut drawing = format!(
+            "cycle {}  X={}  signal strength {}\n",
+            self.cycle, self.register, self.signal_strength
+        );
 
-    println!("Result: {}", result_cycles);
+        for pixel in 0..pixels {
+            drawing.push_str(match self.screen.get(pixel) {
+                Some(drawn) => drawn,
+                None if pixel == self.screen.len() => "@",
+                None => " ",
+            });
+            if pixel % 40 == 39 {
+                drawing.push('\n');
+            }
+        }
+        drawing.extend(
+            (0..40).map(|column| match (column - self.register).abs() <= 1 {
+                true => '^',
+                false => ' ',
+            }),
+        );
+        drawing.push('\n');
 
-    screen.chunks(40).for_each(|chunk| {
-        println!("{}", chunk.join(""));
-    });
+        drawing
+    }
 }
@@ -1,11 +1,101 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
+use crate::{
+    error,
+    error::Line,
+    params::{Parameter, Params},
+    Maybe, ParseError, Solution,
 };
 
-const ROUNDS: usize = 10000;
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Vec<Monkey>;
+    type Answer1 = Maybe<usize>;
+    type Answer2 = Maybe<usize>;
+
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter {
+            name: "rounds_with_relief",
+            default: 20,
+            min: 0,
+            max: 1_000_000,
+            description: "rounds of part 1, worry levels are divided by 3 after inspection",
+        },
+        Parameter {
+            name: "rounds_without_relief",
+            default: 10_000,
+            min: 0,
+            max: 1_000_000,
+            description: "rounds of part 2",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let lines = error::lines(Self::DAY, input).collect::<Vec<Line>>();
+
+        let descriptions = lines
+            .chunks(7)
+            .map(|s| {
+                let mut temp_s = s.to_vec();
+                temp_s.retain(|s| !s.text.is_empty());
+                temp_s
+            })
+            .filter(|description| !description.is_empty())
+            .collect::<Vec<Vec<Line>>>();
+        let monkeys = descriptions
+            .iter()
+            .map(|description| Monkey::new(description))
+            .collect::<Result<Vec<Monkey>, ParseError>>()?;
+
+        // the monkey business is the product of the two most active monkeys
+        if monkeys.len() < 2 {
+            return Err(ParseError::new(Self::DAY, input, "at least two monkeys"));
+        }
+
+        // the worry levels are kept below the common multiple of every divisor
+        let mut multiple = 1usize;
+        for (description, monkey) in descriptions.iter().zip(&monkeys) {
+            multiple = match common_multiple(&[multiple, monkey.test_division]) {
+                Some(multiple) => multiple,
+                None => {
+                    let line = description[3];
+                    return Err(line.error(line.text, "divisors with a common multiple below 2^64"));
+                }
+            };
+        }
+
+        // every monkey should throw to an existing monkey, every description has 6 lines by now
+        for (description, monkey) in descriptions.iter().zip(&monkeys) {
+            for (line, target) in [
+                (description[4], monkey.monkey_t),
+                (description[5], monkey.monkey_f),
+            ] {
+                if target >= monkeys.len() {
+                    let monkey = line.text.rsplit(' ').next().unwrap_or(line.text);
+                    return Err(line.error(
+                        monkey,
+                        format!("a monkey between 0 and {}", monkeys.len() - 1),
+                    ));
+                }
+            }
+        }
+
+        Ok(monkeys)
+    }
+
+    fn part1(monkeys: &Self::Input, params: &Params) -> Self::Answer1 {
+        monkey_business(monkeys, params.get("rounds_with_relief"), true)
+            .map_or(Maybe::Missing("worry levels beyond 2^64"), Maybe::Found)
+    }
+
+    fn part2(monkeys: &Self::Input, params: &Params) -> Self::Answer2 {
+        monkey_business(monkeys, params.get("rounds_without_relief"), false)
+            .map_or(Maybe::Missing("worry levels beyond 2^64"), Maybe::Found)
+    }
+}
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Operation {
     Square,
     Double,
@@ -13,8 +103,8 @@ enum Operation {
     Multiply(usize),
 }
 
-#[derive(Debug)]
-struct Monkey {
+#[derive(Debug, Clone)]
+pub struct Monkey {
     items: Vec<usize>,
     test_division: usize,
     inspections: usize,
@@ -23,30 +113,29 @@ struct Monkey {
     monkey_f: usize,
 }
 
-fn main() {
-    let file = File::open("./data/day11.input").expect("file not found!");
-    let input = io::BufReader::new(file)
-        .lines()
-        .flatten()
-        .collect::<Vec<String>>();
-
-    let mut monkeys = input
-        .chunks(7)
-        .map(|s| {
-            let mut temp_s = s.to_vec();
-            temp_s.retain(|s| !s.is_empty());
-            Monkey::new(&temp_s.join("\n"))
-        })
-        .collect::<Vec<Monkey>>();
+/// returns the product of the numbers, a common multiple of all of them, none if it overflows
+fn common_multiple(numbers: &[usize]) -> Option<usize> {
+    numbers
+        .iter()
+        .try_fold(1usize, |multiple, number| multiple.checked_mul(*number))
+}
 
-    let common_multiple = monkeys.iter().fold(1, |prev, m| prev * m.test_division) * 3;
-    for _round in 0..ROUNDS {
+/// returns the monkey business after `rounds` rounds,
+/// `relief` divides the worry level by three after every inspection,
+/// none if the worry levels or the monkey business overflow
+fn monkey_business(monkeys: &[Monkey], rounds: usize, relief: bool) -> Option<usize> {
+    let mut monkeys = monkeys.to_vec();
+
+    let divisors = monkeys.iter().map(|m| m.test_division).collect::<Vec<_>>();
+    let common_multiple =
+        common_multiple(&divisors).expect("The parser checks the common multiple");
+    for _round in 0..rounds {
         // loop over all monkeys
         for monkey_index in 0..monkeys.len() {
             let monkey = &mut monkeys[monkey_index];
 
             // (item, monkey_to_throw_to)
-            let item_moves: Vec<(usize, usize)> = monkey.inspect(common_multiple);
+            let item_moves: Vec<(usize, usize)> = monkey.inspect(common_multiple, relief)?;
 
             item_moves.iter().for_each(|(item, monkey_index)| {
                 monkeys[*monkey_index].receive(*item);
@@ -54,13 +143,14 @@ fn main() {
         }
     }
 
-    monkeys.sort_by(|a, b| b.inspections.cmp(&a.inspections));
-    let result = monkeys[0].inspections * monkeys[1].inspections;
-    println!("Result: {:?}", result);
+    monkeys.sort_by_key(|m| std::cmp::Reverse(m.inspections));
+    monkeys[0].inspections.checked_mul(monkeys[1].inspections)
 }
 
 impl Monkey {
-    fn inspect(&mut self, worry_divider: usize) -> Vec<(usize, usize)> {
+    /// returns every item with the monkey it is thrown to, none if a worry level overflows,
+    /// without relief the worry levels are kept modulo the common multiple of the divisors
+    fn inspect(&mut self, worry_divider: usize, relief: bool) -> Option<Vec<(usize, usize)>> {
         self.items
             .drain(..)
             .map(|current_item| {
@@ -70,26 +160,27 @@ impl Monkey {
                 // add an inspection to the monkey
                 self.inspections += 1;
 
-                // do the operation on the item
+                // do the operation on the item, which can't overflow twice the width
+                let current_item = current_item as u128;
                 let new_item = match self.operation {
                     Operation::Square => current_item * current_item,
                     Operation::Double => current_item * 2,
-                    Operation::Add(operand) => current_item + operand,
-                    Operation::Multiply(operand) => current_item * operand,
+                    Operation::Add(operand) => current_item + operand as u128,
+                    Operation::Multiply(operand) => current_item * operand as u128,
                 };
 
-                // PART 1
-                // let after_inspection_item = new_item / 3;
-                // ----------------------------------------
-                // PART 2
-                let after_inspection_item = new_item % worry_divider;
+                let after_inspection_item = if relief {
+                    usize::try_from(new_item / 3).ok()?
+                } else {
+                    (new_item % worry_divider as u128) as usize
+                };
 
                 // perform the division check
                 // insert new item to other monkey
                 if after_inspection_item % self.test_division == 0 {
-                    (after_inspection_item, self.monkey_t)
+                    Some((after_inspection_item, self.monkey_t))
                 } else {
-                    (after_inspection_item, self.monkey_f)
+                    Some((after_inspection_item, self.monkey_f))
                 }
             })
             .collect()
@@ -99,87 +190,62 @@ impl Monkey {
         self.items.push(item);
     }
 
-    fn new(description: &str) -> Self {
-        assert!(
-            description.lines().count() == 6,
-            "Could not parse the Monkey description, line numbers not correct"
-        );
-
-        let mut description_iter = description.lines();
-        let _name: String = description_iter
-            .next()
-            .unwrap()
-            .split_once(' ')
-            .unwrap()
-            .1
-            .replace(':', "")
-            .parse()
-            .unwrap();
-
-        let items = description_iter
-            .next()
-            .unwrap()
-            .split_once(": ")
-            .unwrap()
-            .1
-            .split(", ")
-            .map(|n| n.parse::<usize>().unwrap())
-            .collect::<Vec<usize>>();
-
-        let operation_vec = description_iter
-            .next()
-            .unwrap()
-            .split_once('=')
-            .unwrap()
-            .1
-            .trim()
-            .split(' ')
-            .collect::<Vec<&str>>();
+    fn new(description: &[Line]) -> Result<Self, ParseError> {
+        let [name_line, items_line, operation_line, test_line, true_line, false_line] = description
+        else {
+            let line = description[0];
+            return Err(line.error(line.text, "a monkey description of 6 lines"));
+        };
+
+        if !name_line.text.starts_with("Monkey ") {
+            return Err(name_line.error(name_line.text, "a monkey like `Monkey 0:`"));
+        }
+
+        let (_, items_str) =
+            items_line.split_once(":", "starting items like `Starting items: 79, 98`")?;
+        let items = items_str
+            .split(',')
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(|n| items_line.parse::<usize>(n, "a worry level"))
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+
+        let (_, operation_str) =
+            operation_line.split_once("=", "an operation like `Operation: new = old * 19`")?;
+        let operation_vec = operation_str.trim().split(' ').collect::<Vec<&str>>();
 
         let operation = match operation_vec[..] {
             ["old", "+", "old"] => Operation::Double,
             ["old", "*", "old"] => Operation::Square,
-            ["old", "*", a] => Operation::Multiply(a.parse::<usize>().unwrap()),
-            ["old", "+", a] => Operation::Add(a.parse().unwrap()),
+            ["old", "*", a] => Operation::Multiply(operation_line.parse(a, "an operand")?),
+            ["old", "+", a] => Operation::Add(operation_line.parse(a, "an operand")?),
             _ => {
-                panic!("Can't parse operation")
+                return Err(operation_line.error(
+                    operation_str,
+                    "`old + N`, `old * N`, `old + old` or `old * old`",
+                ))
             }
         };
 
-        let test_division = description_iter
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse::<usize>()
-            .unwrap();
-
-        let monkey_t = description_iter
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse::<usize>()
-            .unwrap();
-
-        let monkey_f = description_iter
-            .next()
-            .unwrap()
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .parse::<usize>()
-            .unwrap();
-
-        Monkey {
+        let last_number = |line: &Line, expected| {
+            let last = line.text.split_whitespace().last().unwrap_or(line.text);
+            line.parse::<usize>(last, expected)
+        };
+
+        let test_division = last_number(test_line, "a test like `Test: divisible by 23`")?;
+        if test_division == 0 {
+            return Err(test_line.error(test_line.text, "a divisor larger than 0"));
+        }
+        let monkey_t = last_number(true_line, "a monkey like `If true: throw to monkey 2`")?;
+        let monkey_f = last_number(false_line, "a monkey like `If false: throw to monkey 3`")?;
+
+        Ok(Monkey {
             items,
             test_division,
             inspections: 0,
             operation,
             monkey_t,
             monkey_f,
-        }
+        })
     }
 }
@@ -1,14 +1,91 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-    str::FromStr,
-};
+use std::{cmp::Ordering, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tracing::{debug, trace};
+
+use crate::{error, error::Line, params::Params, ParseError, Solution};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    /// pairs of packets
+    type Input = Vec<(Group, Group)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let input = error::lines(Self::DAY, input).collect::<Vec<Line>>();
+
+        input
+            .chunks(3)
+            .map(|s| {
+                let mut temp_s = s.to_vec();
+                temp_s.retain(|s| !s.text.is_empty());
+
+                let [packet_1, packet_2] = temp_s[..] else {
+                    return Err(s[0].error(s[0].text, "a pair of packets followed by a blank line"));
+                };
+
+                let parse_packet =
+                    |line: Line| Group::from_str(line.text).map_err(|e| e.below(line.number - 1));
+                Ok((parse_packet(packet_1)?, parse_packet(packet_2)?))
+            })
+            .collect()
+    }
+
+    fn part1(groups: &Self::Input, _params: &Params) -> Self::Answer1 {
+        // check ordering for each group
+        groups
+            .iter()
+            .enumerate()
+            .map(|(group_index, (unit_1, unit_2))| {
+                let ordered = match are_groups_ordered(unit_1, unit_2) {
+                    Some(r) => r,
+                    None => unit_1.data.len() < unit_2.data.len(),
+                };
+
+                (group_index, ordered)
+            })
+            .filter(|(index, ordered)| {
+                debug!(index = index + 1, ordered, "compared group");
+                *ordered
+            })
+            .map(|(index, _)| index + 1)
+            .sum::<usize>()
+    }
+
+    fn part2(groups: &Self::Input, _params: &Params) -> Self::Answer2 {
+        let dividers = [
+            Group {
+                data: vec![Unit::ArrayUnit(vec![Unit::IntUnit(2)])],
+            },
+            Group {
+                data: vec![Unit::ArrayUnit(vec![Unit::IntUnit(6)])],
+            },
+        ];
+
+        let mut group_vector = groups
+            .iter()
+            .flat_map(|(unit_1, unit_2)| [unit_1, unit_2])
+            .chain(dividers.iter())
+            .collect::<Vec<&Group>>();
+
+        group_vector.sort_by(|a, b| compare(a, b));
+
+        group_vector
+            .iter()
+            .enumerate()
+            .filter(|(_, g)| dividers.contains(g))
+            .map(|(index, _)| index + 1)
+            .product::<usize>()
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
-struct Group {
+pub struct Group {
     data: Vec<Unit>,
 }
 
@@ -19,113 +96,49 @@ enum Unit {
 }
 
 impl FromStr for Group {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let line = Line::single(Day13::DAY, s);
+
         match serde_json::from_str(s) {
             Ok(Value::Array(v)) => {
-                let data = v.iter().map(Unit::from_json_value).collect::<Vec<Unit>>();
+                let data = v
+                    .iter()
+                    .map(|value| Unit::from_json_value(&line, value))
+                    .collect::<Result<Vec<Unit>, ParseError>>()?;
                 Ok(Group { data })
             }
-            _ => Err(()),
+            Ok(_) => Err(line.error(s, "a packet list like `[1,[2,3]]`")),
+            Err(e) => {
+                let column = e.column().saturating_sub(1).min(s.len());
+                Err(line.error(&s[column..], "a packet list like `[1,[2,3]]`"))
+            }
         }
     }
 }
 
 impl Unit {
-    fn from_json_value(s: &Value) -> Self {
+    fn from_json_value(line: &Line, s: &Value) -> Result<Self, ParseError> {
         match s {
-            Value::Number(v) => Unit::IntUnit(v.as_u64().unwrap() as usize),
-            Value::Array(v) => {
-                Unit::ArrayUnit(v.iter().map(Unit::from_json_value).collect::<Vec<Unit>>())
+            Value::Number(v) if v.is_u64() => Ok(Unit::IntUnit(v.as_u64().unwrap() as usize)),
+            Value::Array(v) => Ok(Unit::ArrayUnit(
+                v.iter()
+                    .map(|value| Unit::from_json_value(line, value))
+                    .collect::<Result<Vec<Unit>, ParseError>>()?,
+            )),
+            _ => {
+                let text = s.to_string();
+                let offending = line
+                    .text
+                    .find(&text)
+                    .map_or(line.text, |index| &line.text[index..index + text.len()]);
+                Err(line.error(offending, "a non-negative integer or a list"))
             }
-            _ => panic!("Oeps, fucked up parsing the unit"),
         }
     }
 }
 
-fn main() {
-    let file = File::open("./data/day13.input").expect("file not found!");
-    let input = io::BufReader::new(file)
-        .lines()
-        .flatten()
-        .collect::<Vec<String>>();
-
-    let groups: Vec<Vec<String>> = input
-        .chunks(3)
-        .map(|s| {
-            let mut temp_s = s.to_vec();
-            temp_s.retain(|s| !s.is_empty());
-            temp_s
-        })
-        .collect();
-
-    // check ordering for each group
-    let result = groups
-        .iter()
-        .enumerate()
-        .map(|(group_index, groups)| {
-            assert_eq!(groups.len(), 2);
-
-            let unit_1: Group = Group::from_str(&groups[0]).unwrap();
-            let unit_2: Group = Group::from_str(&groups[1]).unwrap();
-
-            let ordered = match are_groups_ordered(&unit_1, &unit_2) {
-                Some(r) => r,
-                None => unit_1.data.len() < unit_2.data.len(),
-            };
-
-            (group_index, ordered)
-        })
-        .filter(|(_index, ordered)| {
-            // println!("Index {} - Ordered {:?}", index + 1, ordered);
-            *ordered
-        })
-        .map(|(index, _)| index + 1)
-        .sum::<usize>();
-
-    println!("Result: {}", result);
-
-    let mut group_vector = groups
-        .iter()
-        .flatten()
-        .map(|a| Group::from_str(a).unwrap())
-        .collect::<Vec<Group>>();
-
-    group_vector.push(Group {
-        data: vec![Unit::ArrayUnit(vec![Unit::IntUnit(2)])],
-    });
-
-    group_vector.push(Group {
-        data: vec![Unit::ArrayUnit(vec![Unit::IntUnit(6)])],
-    });
-
-    group_vector.sort_by(|a, b| match are_groups_ordered(a, b) {
-        Some(true) => std::cmp::Ordering::Less,
-        Some(false) => std::cmp::Ordering::Greater,
-        None => std::cmp::Ordering::Equal,
-    });
-
-    let part2 = group_vector
-        .iter()
-        .enumerate()
-        .filter(|(_, g)| {
-            *g == &Group {
-                data: vec![Unit::ArrayUnit(vec![Unit::IntUnit(2)])],
-            } || *g
-                == &Group {
-                    data: vec![Unit::ArrayUnit(vec![Unit::IntUnit(6)])],
-                }
-        })
-        .map(|(index, a)| {
-            println!("{:?}", a);
-            index + 1
-        })
-        .product::<usize>();
-
-    println!("Part 2: {}", part2);
-}
-
 fn check_array_unit_ordered(part_1: &[Unit], part_2: &[Unit]) -> Option<bool> {
     let result = part_1
         .iter()
@@ -145,6 +158,16 @@ fn check_array_unit_ordered(part_1: &[Unit], part_2: &[Unit]) -> Option<bool> {
     }
 }
 
+/// returns the order of two packets, packets which only differ in the nesting of
+/// single integers, like `[1]` and `[[1]]`, are equal
+pub fn compare(part_1: &Group, part_2: &Group) -> Ordering {
+    match are_groups_ordered(part_1, part_2) {
+        Some(true) => Ordering::Less,
+        Some(false) => Ordering::Greater,
+        None => Ordering::Equal,
+    }
+}
+
 fn are_groups_ordered(part_1: &Group, part_2: &Group) -> Option<bool> {
     check_array_unit_ordered(&part_1.data, &part_2.data)
 }
@@ -169,7 +192,7 @@ fn is_ordered(part_1: &Unit, part_2: &Unit) -> Option<bool> {
         }
     };
 
-    // println!("Comparing {:?} and {:?} => {:?}", part_1, part_2, result);
+    trace!(?part_1, ?part_2, ?result, "comparing");
 
     result
 }
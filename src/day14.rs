@@ -1,159 +1,247 @@
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{self, BufRead},
+use tracing::{debug, trace};
+
+use crate::{
+    error,
+    geom::{Direction8, Point2},
+    grid::Position,
+    params::Params,
+    viz::{Render, Simulation},
+    Grid, ParseError, Solution,
 };
 
-#[derive(Eq, PartialEq, Hash, Debug, Clone)]
-struct Position(isize, isize);
+pub struct Day14;
 
-impl Position {
-    fn fall_down(&mut self) {
-        self.1 += 1;
-    }
+impl Solution for Day14 {
+    const DAY: u8 = 14;
 
-    fn peek_fall_down(&self) -> Position {
-        Position(self.0, self.1 + 1)
-    }
+    /// positions occupied by rock
+    type Input = Grid<bool>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    fn peek_fall_right(&self) -> Position {
-        Position(self.0 + 1, self.1 + 1)
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_rocks(input)
     }
 
-    fn fall_right(&mut self) {
-        self.1 += 1;
-        self.0 += 1;
+    fn part1(rocks: &Self::Input, _params: &Params) -> Self::Answer1 {
+        pour_sand(rocks, false)
     }
 
-    fn fall_left(&mut self) {
-        self.1 += 1;
-        self.0 -= 1;
+    fn part2(rocks: &Self::Input, _params: &Params) -> Self::Answer2 {
+        pour_sand(rocks, true)
     }
+}
+
+/// the most cells of a dense grid of the cave, deeper caves are sparse
+const MAX_DENSE_CELLS: usize = 1 << 24;
 
-    fn peek_fall_left(&self) -> Position {
-        Position(self.0 - 1, self.1 + 1)
+/// where the sand is pouring from
+const SOURCE: Point2 = Point2::new(500, 0);
+
+fn parse_rocks(input: &str) -> Result<Grid<bool>, ParseError> {
+    let mut occupied_positions = Grid::sparse(0, 0, false);
+    for line in error::lines(Day14::DAY, input) {
+        let points = line
+            .text
+            .split(" -> ")
+            .map(|point| {
+                let (x, y) = point
+                    .split_once(',')
+                    .ok_or_else(|| line.error(point, "a point like `498,4`"))?;
+                let x = line.parse::<usize>(x, "a non-negative x coordinate")?;
+                let y = line.parse::<usize>(y, "a non-negative y coordinate")?;
+                Ok((x, y))
+            })
+            .collect::<Result<Vec<Position>, ParseError>>()?;
+
+        for window in points.windows(2) {
+            let ((a1, a2), (b1, b2)) = (window[0], window[1]);
+
+            if a1 == b1 && a2 != b2 {
+                for n in a2.min(b2)..=a2.max(b2) {
+                    occupied_positions.insert((a1, n), true);
+                }
+            } else if a2 == b2 && a1 != b1 {
+                for n in a1.min(b1)..=a1.max(b1) {
+                    occupied_positions.insert((n, a2), true);
+                }
+            } else {
+                return Err(line.error(
+                    line.text,
+                    "horizontal or vertical lines between distinct points",
+                ));
+            }
+        }
     }
 
-    fn reset(&mut self) {
-        self.0 = 500;
-        self.1 = 0;
+    if occupied_positions.height() == 0 {
+        return Err(ParseError::new(
+            Day14::DAY,
+            input,
+            "at least one line of rock",
+        ));
     }
 
-    /// returns true if falling down, it reaches the floor
-    fn can_fall_regarding_floor(&self, floor: isize) -> bool {
-        self.1 + 1 < floor
+    Ok(occupied_positions)
+}
+
+/// returns the amount of sand units which come to rest,
+/// without a floor the sand stops pouring as soon as a unit falls into the abyss
+fn pour_sand(rocks: &Grid<bool>, has_floor: bool) -> usize {
+    let mut sand = Sand::new(rocks, has_floor);
+    while sand.step() {}
+
+    debug!(sand_unit = sand.resting, has_floor, "poured sand");
+    sand.resting
+}
+
+/// Sand pouring from the source one unit at a time, a unit falls one position per step.
+pub struct Sand {
+    rocks: Grid<bool>,
+    /// positions occupied by rock or sand, shifted right by `shift`
+    occupied: Grid<bool>,
+    /// the sand spreads at most as far left of the source as the floor is deep,
+    /// shifting by the depth keeps every position it reaches at x >= 0
+    shift: isize,
+    floor: isize,
+    has_floor: bool,
+    /// the unit which is falling, none till the next one pours
+    falling: Option<Point2>,
+    /// units which came to rest
+    resting: usize,
+    /// the leftmost and rightmost occupied x
+    columns: (isize, isize),
+}
+
+impl Sand {
+    pub fn new(rocks: &Grid<bool>, has_floor: bool) -> Self {
+        let columns = rocks
+            .stored()
+            .filter(|(_, rock)| **rock)
+            .fold((SOURCE.x, SOURCE.x), |(left, right), ((x, _), _)| {
+                (left.min(x as isize), right.max(x as isize))
+            });
+
+        // the floor is two below the lowest rock
+        let floor = rocks.height() as isize + 1;
+        // every position the sand can reach is within the floor depth of the source, the
+        // triangle is only allocated at once while it is small enough
+        let width = rocks.width().max((SOURCE.x + floor) as usize + 1) + floor as usize;
+        let mut occupied = match width.checked_mul(floor as usize) {
+            Some(cells) if cells <= MAX_DENSE_CELLS => Grid::filled(width, floor as usize, false),
+            _ => Grid::sparse(0, 0, false),
+        };
+        for ((x, y), _) in rocks.stored().filter(|(_, rock)| **rock) {
+            occupied.insert((x + floor as usize, y), true);
+        }
+
+        Sand {
+            rocks: rocks.clone(),
+            occupied,
+            shift: floor,
+            floor,
+            has_floor,
+            falling: None,
+            resting: 0,
+            columns,
+        }
     }
+}
 
-    fn is_in_range(&self, min_x: isize, max_x: isize) -> bool {
-        min_x <= self.0 && max_x >= self.1
+impl Sand {
+    /// returns true if the sand can't fall to the point
+    fn is_occupied(&self, point: Point2) -> bool {
+        Point2::new(point.x + self.shift, point.y)
+            .to_position()
+            .and_then(|position| self.occupied.get(position))
+            == Some(&true)
     }
 
-    fn blocks_source(&self) -> bool {
-        self.0 == 500 && self.1 == 0
+    /// returns the point the sand falls to, trying down, down left and down right in that order
+    fn fall(&self, sand: Point2) -> Option<Point2> {
+        if sand.y + 1 >= self.floor {
+            // the sand rests on the floor
+            return None;
+        }
+
+        [Direction8::S, Direction8::SW, Direction8::SE]
+            .into_iter()
+            .map(|direction| sand + direction.offset())
+            .find(|point| !self.is_occupied(*point))
     }
 }
 
-fn main() {
-    let file = File::open("./data/day14.input").expect("file not found!");
-    let input = io::BufReader::new(file)
-        .lines()
-        .flatten()
-        .collect::<Vec<String>>();
-
-    let mut occupied_positions: HashSet<Position> = HashSet::new();
-    input.iter().for_each(|line_spec| {
-        let points = line_spec
-            .split(" -> ")
-            .map(|point| {
-                let (x, y) = point.split_once(',').unwrap();
-                Position(x.parse().unwrap(), y.parse().unwrap())
-            })
-            .collect::<Vec<Position>>();
-
-        points.windows(2).for_each(|window| {
-            assert_eq!(window.len(), 2, "Window should be of length 2");
-            let point_a = &window[0];
-            let point_b = &window[1];
-
-            match (point_a, point_b) {
-                (Position(a1, a2), Position(b1, b2)) if a1 == b1 && b2 > a2 => {
-                    for n in *a2 as u64..(*b2 as u64 + 1) {
-                        occupied_positions.insert(Position(*a1, n as isize));
-                    }
-                }
-                (Position(a1, a2), Position(b1, b2)) if a1 == b1 && b2 < a2 => {
-                    for n in *b2 as u64..(*a2 as u64 + 1) {
-                        occupied_positions.insert(Position(*a1, n as isize));
-                    }
-                }
-                (Position(a1, a2), Position(b1, b2)) if a2 == b2 && b1 > a1 => {
-                    for n in *a1 as u64..(*b1 as u64 + 1) {
-                        occupied_positions.insert(Position(n as isize, *a2));
-                    }
-                }
-                (Position(a1, a2), Position(b1, b2)) if a2 == b2 && b1 < a1 => {
-                    for n in *b1 as u64..(*a1 as u64 + 1) {
-                        occupied_positions.insert(Position(n as isize, *a2));
-                    }
-                }
-                _ => {
-                    panic!("Could not draw a line")
-                }
-            }
-        });
-    });
-
-    // find min x and max x
-    let max_x = occupied_positions.iter().map(|p| p.0).max().unwrap();
-    let min_x = occupied_positions.iter().map(|p| p.0).min().unwrap();
-    let floor = occupied_positions.iter().map(|p| p.1).max().unwrap() + 2;
-    println!("Floor: {}", floor);
-
-    // Sand is pouring from (500, 0)
-    let mut falling_sand = Position(500, 0);
-    let mut sand_unit = 1;
-    'new_sand: loop {
-        'sand_fall: loop {
-            if !occupied_positions.contains(&falling_sand.peek_fall_down())
-                && falling_sand.can_fall_regarding_floor(floor)
-            {
-                falling_sand.fall_down();
-            } else if !occupied_positions.contains(&falling_sand.peek_fall_left())
-                && falling_sand.can_fall_regarding_floor(floor)
-            {
-                falling_sand.fall_left();
-            } else if !occupied_positions.contains(&falling_sand.peek_fall_right())
-                && falling_sand.can_fall_regarding_floor(floor)
-            {
-                falling_sand.fall_right();
-            } else {
-                // sand can't move
-                occupied_positions.insert(falling_sand.clone());
-                // stop falling
-                break 'sand_fall;
+impl Simulation for Sand {
+    /// lets the falling unit fall one position, or pours the next one
+    fn step(&mut self) -> bool {
+        let Some(falling_sand) = self.falling else {
+            if self.is_occupied(SOURCE) {
+                return false;
             }
+            self.falling = Some(SOURCE);
+            return true;
+        };
 
-            // check if the new position is still in range
-            // if !falling_sand.is_in_range(min_x, max_x) {
-            //     sand_unit -= 1;
-            //     break 'new_sand;
-            // };
+        if let Some(position) = self.fall(falling_sand) {
+            self.falling = Some(position);
+            return true;
         }
 
-        // PART 2
-        if falling_sand.blocks_source() {
-            // stop new_sand
-            break 'new_sand;
-        };
-
+        if !self.has_floor && falling_sand.y + 1 >= self.floor {
+            // below every rock, the sand falls into the abyss
+            return false;
+        }
 
-        // println!("{}", sand_unit);
-        sand_unit += 1;
-        falling_sand.reset();
+        // sand can't move
+        let position = Point2::new(falling_sand.x + self.shift, falling_sand.y)
+            .to_position()
+            .expect("The sand stays within the floor depth of the source");
+        self.occupied.insert(position, true);
+        trace!(sand_unit = self.resting, ?falling_sand, "sand came to rest");
+        self.resting += 1;
+        self.falling = None;
+        self.columns = (
+            self.columns.0.min(falling_sand.x),
+            self.columns.1.max(falling_sand.x),
+        );
+
+        true
     }
+}
+
+impl Render for Sand {
+    /// draws the cave like the puzzle, `#` for rock, `o` for sand, `+` for the source
+    /// and `~` for the falling unit
+    fn render(&self) -> String {
+        let mut drawing = format!("{} units of sand came to rest\n", self.resting);
+
+        for y in 0..self.floor {
+            for x in self.columns.0 - 1..=self.columns.1 + 1 {
+                let point = Point2::new(x, y);
+                let rock = point
+                    .to_position()
+                    .and_then(|position| self.rocks.get(position))
+                    == Some(&true);
+
+                drawing.push(if rock {
+                    '#'
+                } else if self.is_occupied(point) {
+                    'o'
+                } else if self.falling == Some(point) {
+                    '~'
+                } else if point == SOURCE {
+                    '+'
+                } else {
+                    '.'
+                });
+            }
+            drawing.push('\n');
+        }
+        if self.has_floor {
+            drawing.extend((self.columns.0 - 1..=self.columns.1 + 1).map(|_| '#'));
+            drawing.push('\n');
+        }
 
-    // println!("{:?}", occupied_positions);
-    println!("Min x: {:?}", min_x);
-    println!("Max x: {:?}", max_x);
-    println!("Result: {}", sand_unit);
+        drawing
+    }
 }
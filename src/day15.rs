@@ -1,52 +1,132 @@
 use std::{
-    collections::HashMap,
-    fs::File,
-    io::{self, BufRead},
+    collections::{HashMap, HashSet},
     str::FromStr,
 };
 
-#[derive(Debug, Hash, Eq, PartialEq)]
-struct Position(isize, isize);
+use itertools::Itertools;
+use tracing::{debug, trace};
+
+use crate::{
+    error,
+    error::Line,
+    geom::Point2,
+    params::{Parameter, Params},
+    Maybe, ParseError, Solution,
+};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Vec<Sensor>;
+    type Answer1 = usize;
+    type Answer2 = Maybe<isize>;
+
+    // the example of the puzzle uses 10 and 20, see aoc.toml
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter {
+            name: "interested_row",
+            default: 2_000_000,
+            min: -1_000_000_000,
+            max: 1_000_000_000,
+            description: "row on which the positions without a beacon are counted",
+        },
+        Parameter {
+            name: "max_range",
+            default: 4_000_000,
+            min: 0,
+            max: 1_000_000_000,
+            description: "largest x and y coordinate of the distress beacon",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        debug!("creating sensors");
+        let sensors = error::lines(Self::DAY, input)
+            .map(|line| Sensor::from_str(line.text).map_err(|e| e.below(line.number - 1)))
+            .collect::<Result<Vec<Sensor>, ParseError>>()?;
+
+        if sensors.is_empty() {
+            return Err(ParseError::new(Self::DAY, input, "at least one sensor"));
+        }
+
+        Ok(sensors)
+    }
+
+    fn part1(sensors: &Self::Input, params: &Params) -> Self::Answer1 {
+        let interested_row = params.get::<isize>("interested_row");
+        let range = sensors
+            .iter()
+            .fold(HashSet::new(), |mut final_range, sensor| {
+                if let Some(coverage_at_row) = sensor.coverage.get(&interested_row) {
+                    let range = coverage_at_row.0..coverage_at_row.1 + 1;
+                    final_range.extend(range);
+                }
+                final_range
+            })
+            .len();
+        let beacons_on_row = sensors
+            .iter()
+            .filter_map(|s| {
+                if s.closest_beacon.y == interested_row {
+                    Some(&s.closest_beacon)
+                } else {
+                    None
+                }
+            })
+            .unique()
+            .count();
+
+        range - beacons_on_row
+    }
+
+    fn part2(sensors: &Self::Input, params: &Params) -> Self::Answer2 {
+        find_distress_beacon(sensors, params.get("max_range"))
+            .map_or(Maybe::Missing("no distress beacon"), Maybe::Found)
+    }
+}
 
 #[derive(Debug, Clone)]
 struct RowCoverage(isize, isize);
 
 #[derive(Debug)]
-struct Sensor {
-    position: Position,
-    closest_beacon: Position,
+pub struct Sensor {
+    closest_beacon: Point2,
     coverage: HashMap<isize, RowCoverage>,
 }
 
-fn manhattan_distance(position_a: &Position, position_b: &Position) -> usize {
-    (position_a.0 - position_b.0).unsigned_abs() + (position_a.1 - position_b.1).unsigned_abs()
-}
-
 // Sensor at x=2, y=18
-fn get_coordinates(line: &str) -> Position {
-    let mut line_iter = line.split('=');
-    line_iter.next();
-    let x_string = line_iter.next().unwrap().replace(", y", "");
-    let y_string = line_iter.next().unwrap();
-
-    Position(x_string.parse().unwrap(), y_string.parse().unwrap())
+fn get_coordinates(line: &Line, description: &str) -> Result<Point2, ParseError> {
+    let expected = "coordinates like `x=2, y=18`";
+
+    let (_, coordinates) = description
+        .split_once("x=")
+        .ok_or_else(|| line.error(description, expected))?;
+    let (x_string, y_string) = coordinates
+        .split_once(", y=")
+        .ok_or_else(|| line.error(coordinates, expected))?;
+
+    Ok(Point2::new(
+        line.parse(x_string, "an x coordinate")?,
+        line.parse(y_string, "a y coordinate")?,
+    ))
 }
 
 fn generate_coverage(
-    center_position: &Position,
+    center_position: &Point2,
     manh_distance: isize,
 ) -> HashMap<isize, RowCoverage> {
     let mut coverage = HashMap::new();
-    for row_index in center_position.1 - manh_distance..center_position.1 + manh_distance + 1 {
-        let horizontal_radius = manh_distance - (center_position.1 - row_index).abs();
-        // println!("row_index {}", row_index);
-        // println!("horizontal_radius {}", horizontal_radius);
+    for row_index in center_position.y - manh_distance..center_position.y + manh_distance + 1 {
+        let horizontal_radius = manh_distance - (center_position.y - row_index).abs();
+        trace!(row_index, horizontal_radius, "covering row");
 
         coverage.insert(
             row_index,
             RowCoverage(
-                center_position.0 - horizontal_radius,
-                center_position.0 + horizontal_radius,
+                center_position.x - horizontal_radius,
+                center_position.x + horizontal_radius,
             ),
         );
     }
@@ -55,53 +135,34 @@ fn generate_coverage(
 }
 
 impl FromStr for Sensor {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (sensor_desc, closest_beacon_desc) = s.split_once(':').unwrap();
-
-        let sensor_coord = get_coordinates(sensor_desc);
-        let beacon_coord = get_coordinates(closest_beacon_desc);
-        // println!("Sensor: {:?}", &sensor_coord);
-        // println!("Beacon: {:?}", &beacon_coord);
+        let line = Line::single(Day15::DAY, s);
+        let (sensor_desc, closest_beacon_desc) = line.split_once(
+            ":",
+            "a sensor like `Sensor at x=2, y=18: closest beacon is at x=-2, y=15`",
+        )?;
 
-        let distance = manhattan_distance(&sensor_coord, &beacon_coord);
-        // println!("Distance: {}", distance);
+        let sensor_coord = get_coordinates(&line, sensor_desc)?;
+        let beacon_coord = get_coordinates(&line, closest_beacon_desc)?;
+        let distance = sensor_coord.manhattan_distance(beacon_coord);
+        debug!(sensor = ?sensor_coord, beacon = ?beacon_coord, distance, "sensor");
 
         let coverage = generate_coverage(&sensor_coord, distance as isize);
-        // println!("Coverage: {:?}", coverage);
+        trace!(?coverage, "coverage");
 
         Ok(Sensor {
             closest_beacon: beacon_coord,
             coverage,
-            position: sensor_coord,
         })
     }
 }
 
-fn main() {
-    let file = File::open("./data/day15.input").expect("file not found!");
-    let input = io::BufReader::new(file)
-        .lines()
-        .flatten()
-        .collect::<Vec<String>>();
-
-    // TEST
-    // let interested_row = 10;
-    // let max_range = 20;
-
-    // INPUT
-    // let interested_row = 2000000;
-    let max_range = 4_000_000;
-
-    println!("Creating sensors...");
-    let sensors = input
-        .iter()
-        .map(|i| Sensor::from_str(i).unwrap())
-        .collect::<Vec<Sensor>>();
-
-    // PART 2
-    println!("Folding coverage...");
+/// returns the tuning frequency of the only position within `max_range` not covered by a sensor,
+/// none if the sensors leave no such position next to their coverage
+fn find_distress_beacon(sensors: &[Sensor], max_range: isize) -> Option<isize> {
+    debug!("folding coverage");
     let coverage = sensors
         .iter()
         .fold(HashMap::new(), |mut final_map, sensor| {
@@ -117,7 +178,7 @@ fn main() {
                         _ => row_coverage.clone(),
                     };
 
-                    if final_map.get(row_index).is_none() {
+                    if !final_map.contains_key(row_index) {
                         // initialize the range
                         final_map.insert(row_index, vec![new_row_coverage]);
                     } else {
@@ -132,7 +193,7 @@ fn main() {
         });
 
     // Beacon can only be at edge of coverage
-    println!("Searching single space...");
+    debug!("searching the single space");
     for (row_index, row_coverage_vec) in coverage {
         let test = row_coverage_vec.iter().find_map(|row_cov| {
             let min = match row_cov.0 {
@@ -160,34 +221,9 @@ fn main() {
         });
 
         if let Some(horizontal_index) = test {
-            println!("{}, {}", horizontal_index, row_index);
-            println!("Result: {}", horizontal_index * 4000000 + row_index);
-            break;
+            return Some(horizontal_index * 4_000_000 + row_index);
         }
     }
 
-    // println!("Result: {:?}", result);
-
-    // PART 1
-    // let range = sensors
-    //     .iter()
-    //     .fold(HashSet::new(), |mut final_range, sensor| {
-    //         if let Some(coverage_at_row) = sensor.coverage.get(&interested_row) {
-    //             let range = coverage_at_row.0..coverage_at_row.1 + 1;
-    //             final_range.extend(range);
-    //         }
-    //         final_range
-    //     })
-    //     .len();
-    // let beacons_on_row = sensors.iter().filter_map(|s| {
-    //     if s.closest_beacon.1 == interested_row {
-    //         Some(&s.closest_beacon)
-    //     }
-    //     else {
-    //         None
-    //     }
-    // }).unique().count();
-    // println!("Beacons on row: {}", beacons_on_row);
-    //
-    // println!("Range: {}", range - beacons_on_row);
+    None
 }
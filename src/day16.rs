@@ -1,199 +1,210 @@
-use std::{
-    cmp,
-    collections::{HashMap, HashSet},
-    fs::File,
-    io::{self, BufRead},
+use std::{cmp, collections::HashMap};
+
+use tracing::debug;
+
+use crate::{
+    error,
+    graph::{self, Graph, IndexedGraph},
+    params::Params,
+    ParseError, Solution,
 };
 
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = TunnelSystem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let tunnel_system = TunnelSystem::new(input)?;
+        debug!(graph = ?tunnel_system, "parsed");
+        Ok(tunnel_system)
+    }
+
+    fn part1(tunnel_system: &Self::Input, _params: &Params) -> Self::Answer1 {
+        tunnel_system.depth_first_search(30, tunnel_system.start, 0, &mut HashMap::new())
+    }
+
+    fn part2(tunnel_system: &Self::Input, _params: &Params) -> Self::Answer2 {
+        debug!("splitting the valves with the elephant");
+
+        let b: usize = (1 << tunnel_system.flowing_valves.len()) - 1;
+
+        let mut m = 0;
+
+        let mut cache = HashMap::new();
+
+        // split the valves between me and the elephant
+        for i in 0..b.div_ceil(2) {
+            m = cmp::max(
+                m,
+                tunnel_system.depth_first_search(26, tunnel_system.start, i, &mut cache)
+                    + tunnel_system.depth_first_search(26, tunnel_system.start, b ^ i, &mut cache),
+            );
+        }
+
+        m
+    }
+}
+
+/// The valves are numbered in the order of the input.
 #[derive(Debug)]
-struct TunnelSystem {
-    tunnels: HashMap<String, Vec<String>>,
-    valves: HashMap<String, usize>,
+pub struct TunnelSystem {
+    /// valves reachable through a tunnel from every valve
+    tunnels: Vec<Vec<usize>>,
+    /// flow rate of every valve
+    rates: Vec<usize>,
+    /// the valve to start from, AA
+    start: usize,
+    /// valves with a flow rate, bit i of the opened valves is the ith valve of this list
+    flowing_valves: Vec<usize>,
     /// distance from Valve source to Valve destination
-    distances: HashMap<(String, String), usize>,
-    /// indices of valves
-    indices: HashMap<String, usize>,
+    distances: Vec<Vec<Option<usize>>>,
 }
 
-#[derive(Hash, Debug, Eq, PartialEq)]
-struct CacheEntry {
-    time: isize,
-    valve: String,
-    opened_valves: usize,
-}
+/// the most valves with a flow rate, one bit of the opened valves each
+/// while part 2 still shifts a bit past the last one
+const MAX_FLOWING_VALVES: usize = usize::BITS as usize - 1;
 
-impl TunnelSystem {
-    fn new(input: &[String]) -> Self {
-        let mut tunnels: HashMap<String, Vec<String>> = HashMap::new();
-        let mut valves: HashMap<String, usize> = HashMap::new();
-        let mut indices: HashMap<String, usize> = HashMap::new();
-        input.iter().for_each(|s| {
-            let (valve_desc, tunnel_desc) = s.split_once(';').unwrap();
+/// (time, valve, opened valves)
+type CacheEntry = (isize, usize, usize);
 
-            let valve_name = valve_desc.split(' ').nth(1).unwrap();
-            let (_, rate_str) = valve_desc.split_once('=').unwrap();
-            let rate: usize = rate_str.parse().unwrap();
+impl TunnelSystem {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let mut valves: HashMap<&str, usize> = HashMap::new();
+        let mut rates = vec![];
+        let mut tunnel_descriptions = vec![];
+        for line in error::lines(Day16::DAY, input) {
+            let (valve_desc, tunnel_desc) = line.split_once(
+                ";",
+                "a valve like `Valve AA has flow rate=0; tunnels lead to valves DD, II`",
+            )?;
+
+            let valve_name = valve_desc
+                .split(' ')
+                .nth(1)
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| line.error(valve_desc, "a valve like `Valve AA`"))?;
+            let (_, rate_str) = valve_desc
+                .split_once('=')
+                .ok_or_else(|| line.error(valve_desc, "a flow rate like `rate=13`"))?;
+            let rate: usize = line.parse(rate_str, "a flow rate")?;
+            // the opened valves are a bitmask of the flowing valves
+            if rate != 0 && rates.iter().filter(|rate| **rate != 0).count() == MAX_FLOWING_VALVES {
+                return Err(line.error(
+                    rate_str,
+                    format!("at most {} valves with a flow rate", MAX_FLOWING_VALVES),
+                ));
+            }
 
             let tunnels_str = match tunnel_desc.split_once("valves ") {
                 Some((_, tunnel_valves)) => tunnel_valves,
-                None => tunnel_desc.split_once("valve ").unwrap().1,
+                None => {
+                    tunnel_desc
+                        .split_once("valve ")
+                        .ok_or_else(|| {
+                            line.error(tunnel_desc, "tunnels like `tunnels lead to valves DD, II`")
+                        })?
+                        .1
+                }
             };
 
-            let tunnels_for_valve: Vec<String> =
-                tunnels_str.split(", ").map(|s| s.to_owned()).collect();
+            valves.insert(valve_name, rates.len());
+            rates.push(rate);
+            tunnel_descriptions.push((line, tunnels_str));
+        }
 
-            valves.insert(valve_name.to_owned(), rate);
-            tunnels.insert(valve_name.to_owned(), tunnels_for_valve);
-        });
+        // every tunnel should lead to a known valve
+        let tunnels = tunnel_descriptions
+            .into_iter()
+            .map(|(line, tunnels_str)| {
+                tunnels_str
+                    .split(", ")
+                    .map(|name| {
+                        valves
+                            .get(name)
+                            .copied()
+                            .ok_or_else(|| line.error(name, "a tunnel to a valve of the input"))
+                    })
+                    .collect::<Result<Vec<usize>, ParseError>>()
+            })
+            .collect::<Result<Vec<Vec<usize>>, ParseError>>()?;
 
-        valves
-            .iter()
-            .filter(|v| v.1 != &0)
-            .enumerate()
-            .for_each(|(i, valve)| {
-                indices.insert(valve.0.to_string(), i);
-            });
+        let Some(start) = valves.get("AA").copied() else {
+            return Err(ParseError::new(Day16::DAY, "", "a starting valve AA"));
+        };
 
-        TunnelSystem {
+        let mut tunnel_system = TunnelSystem {
             tunnels,
-            valves,
-            distances: HashMap::new(),
-            indices,
-        }
-    }
-
-    /// applies Floyd-Warshall to the graph (removes unreachable connections)
-    fn compute_distances(&mut self) {
-        self.tunnels.iter().for_each(|(source, destinations)| {
-            destinations.iter().for_each(|dest| {
-                self.distances
-                    .insert((source.to_owned(), dest.to_owned()), 1);
-            });
-        });
-
-        self.valves.iter().for_each(|(valve, _rate)| {
-            self.distances
-                .insert((valve.to_owned(), valve.to_owned()), 0);
-        });
-
-        self.valves.iter().for_each(|(valve, _)| {
-            self.valves.iter().for_each(|(source, _)| {
-                self.valves.iter().for_each(|(destination, _)| {
-                    let test_distance = self
-                        .distances
-                        .get(&(source.to_owned(), valve.to_owned()))
-                        .unwrap_or(&usize::MAX)
-                        .saturating_add(
-                            *self
-                                .distances
-                                .get(&(valve.to_owned(), destination.to_owned()))
-                                .unwrap_or(&usize::MAX),
-                        );
-                    if self
-                        .distances
-                        .get(&(source.to_owned(), destination.to_owned()))
-                        .unwrap_or(&usize::MAX)
-                        > &test_distance
-                    {
-                        self.distances
-                            .insert((source.to_owned(), destination.to_owned()), test_distance);
-                    };
-                });
-            })
-        });
+            flowing_valves: (0..rates.len())
+                .filter(|valve| rates[*valve] != 0)
+                .collect(),
+            rates,
+            start,
+            distances: vec![],
+        };
+        tunnel_system.distances = graph::floyd_warshall(&tunnel_system);
 
-        self.distances
-            .retain(|k, _v| k.1 != "AA" && k.0 != k.1 && self.valves.get(&k.1).unwrap() != &0);
+        Ok(tunnel_system)
     }
 
-    /// returns the max amount of pressure which can be released
+    /// returns the max amount of pressure which can be released,
+    /// every flowing valve in `opened_valves` is left alone
     fn depth_first_search(
         &self,
         time: isize,
-        current_valve: &str,
-        mut opened_valves: usize,
+        current_valve: usize,
+        opened_valves: usize,
         cache: &mut HashMap<CacheEntry, usize>,
     ) -> usize {
-        let mut max_pressure = 0;
-
-        let cache_key = CacheEntry {
-            time,
-            valve: current_valve.to_owned(),
-            opened_valves,
-        };
+        let cache_key = (time, current_valve, opened_valves);
 
         if let Some(cached_value) = cache.get(&cache_key) {
             return *cached_value;
         };
 
-        for ((s, neighbour), dist) in self
-            .distances
-            .iter()
-            .filter(|((s, _), _)| s == current_valve)
-        {
-            if opened_valves & (1 << self.indices.get(neighbour).unwrap()) != 0 {
+        let mut max_pressure = 0;
+        for (index, neighbour) in self.flowing_valves.iter().enumerate() {
+            if opened_valves & (1 << index) != 0 {
+                continue;
+            };
+            let Some(dist) = self.distances[current_valve][*neighbour] else {
                 continue;
             };
 
-            let rem_time = time - 1 - *dist as isize;
+            // walk to the valve and open it
+            let rem_time = time - 1 - dist as isize;
             if rem_time <= 0 {
                 continue;
             };
 
-            opened_valves |= 1 << self.indices.get(s).unwrap_or(&self.indices.len());
-
-            let additional_pressure = rem_time as usize * self.valves.get(neighbour).unwrap();
+            let additional_pressure = rem_time as usize * self.rates[*neighbour];
 
             max_pressure = cmp::max(
                 max_pressure,
-                self.depth_first_search(rem_time, neighbour, opened_valves, cache)
+                self.depth_first_search(rem_time, *neighbour, opened_valves | (1 << index), cache)
                     + additional_pressure,
             );
         }
 
-        let new_cache_key = CacheEntry {
-            time,
-            valve: current_valve.to_owned(),
-            opened_valves,
-        };
-
-        cache.insert(new_cache_key, max_pressure);
+        cache.insert(cache_key, max_pressure);
         max_pressure
     }
 }
 
-fn main() {
-    let file = File::open("./data/day16.input").expect("file not found!");
-    let input = io::BufReader::new(file)
-        .lines()
-        .flatten()
-        .collect::<Vec<String>>();
-
-    let mut tunnel_system = TunnelSystem::new(&input);
-
-    tunnel_system.compute_distances();
-
-    println!("Computing result");
-    // println!(
-    //     "Result: {:?}",
-    //     tunnel_system.depth_first_search(30, "AA", 0, &mut HashMap::new())
-    // )
+impl Graph for TunnelSystem {
+    type Node = usize;
 
-    let b: usize = (1 << tunnel_system.indices.len()) - 1;
-
-    let mut m = 0;
-
-    let mut cache = HashMap::new();
-
-    println!("{}", b);
-    for i in 0..(b + 1) / 2 {
-        m = cmp::max(
-            m,
-            tunnel_system.depth_first_search(26, "AA", i, &mut cache)
-                + tunnel_system.depth_first_search(26, "AA", b ^ i, &mut cache),
-        );
+    fn neighbours(&self, valve: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.tunnels[valve].iter().map(|neighbour| (*neighbour, 1))
     }
+}
 
-    println!("Result: {}", m);
-    // println!("Graph: {:?}", tunnel_system);
+impl IndexedGraph for TunnelSystem {
+    fn node_count(&self) -> usize {
+        self.rates.len()
+    }
 }
@@ -1,14 +1,77 @@
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{self, BufRead},
+use std::collections::HashMap;
+
+use tracing::{debug, trace};
+
+use crate::{
+    error,
+    geom::{Direction, Point2},
+    params::{Parameter, Params},
+    viz::{Render, Simulation},
+    Grid, ParseError, Solution,
 };
 
 const CAVE_WIDTH: usize = 7;
 
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    /// directions of the jets of hot gas
+    type Input = Vec<Direction>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter {
+            name: "short_tower_blocks",
+            default: 2022,
+            min: 0,
+            max: 1_000_000_000_000,
+            description: "blocks dropped in part 1",
+        },
+        Parameter {
+            name: "tall_tower_blocks",
+            default: 1_000_000_000_000,
+            min: 0,
+            max: 1_000_000_000_000_000,
+            description: "blocks dropped in part 2",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let Some(line) = error::lines(Self::DAY, input)
+            .next()
+            .filter(|line| !line.text.is_empty())
+        else {
+            return Err(ParseError::new(
+                Self::DAY,
+                "",
+                "a pattern of jets like `>><<>`",
+            ));
+        };
+
+        line.text
+            .char_indices()
+            .map(|(index, c)| match c {
+                '<' | '>' => Ok(Direction::try_from(c).expect("An arrow is a direction")),
+                _ => Err(line.error(&line.text[index..], "a jet direction < or >")),
+            })
+            .collect()
+    }
+
+    fn part1(movements: &Self::Input, params: &Params) -> Self::Answer1 {
+        tower_height(movements, params.get("short_tower_blocks"))
+    }
+
+    fn part2(movements: &Self::Input, params: &Params) -> Self::Answer2 {
+        tower_height(movements, params.get("tall_tower_blocks"))
+    }
+}
+
 #[derive(Clone, Debug)]
 struct Block {
-    pub mask: Vec<(isize, isize)>,
+    pub mask: Vec<Point2>,
 }
 
 enum BlockType {
@@ -23,79 +86,79 @@ impl Block {
     fn new(block_type: BlockType) -> Self {
         match block_type {
             BlockType::HorizontalBar => Block {
-                mask: vec![(0, 0), (1, 0), (2, 0), (3, 0)],
+                mask: vec![
+                    Point2::new(0, 0),
+                    Point2::new(1, 0),
+                    Point2::new(2, 0),
+                    Point2::new(3, 0),
+                ],
             },
             BlockType::Cross => Block {
-                mask: vec![(0, 1), (2, 1), (1, 0), (1, 1), (1, 2)],
+                mask: vec![
+                    Point2::new(0, 1),
+                    Point2::new(2, 1),
+                    Point2::new(1, 0),
+                    Point2::new(1, 1),
+                    Point2::new(1, 2),
+                ],
             },
             BlockType::L => Block {
-                mask: vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+                mask: vec![
+                    Point2::new(0, 0),
+                    Point2::new(1, 0),
+                    Point2::new(2, 0),
+                    Point2::new(2, 1),
+                    Point2::new(2, 2),
+                ],
             },
             BlockType::VerticalBar => Block {
-                mask: vec![(0, 0), (0, 1), (0, 2), (0, 3)],
+                mask: vec![
+                    Point2::new(0, 0),
+                    Point2::new(0, 1),
+                    Point2::new(0, 2),
+                    Point2::new(0, 3),
+                ],
             },
             BlockType::Cube => Block {
-                mask: vec![(0, 0), (0, 1), (1, 0), (1, 1)],
+                mask: vec![
+                    Point2::new(0, 0),
+                    Point2::new(0, 1),
+                    Point2::new(1, 0),
+                    Point2::new(1, 1),
+                ],
             },
         }
     }
 
     fn init_start(&mut self, cave: &Cave) {
         let start_position = cave.calc_start_position();
-        self.mask.iter_mut().for_each(|offset| {
-            offset.0 += start_position.0;
-            offset.1 += start_position.1;
-        });
+        self.mask
+            .iter_mut()
+            .for_each(|offset| *offset += start_position);
     }
 
     /// returns false if the movement did not occur
-    fn jet_push(&mut self, direction: &char, cave: &Cave) -> bool {
-        // check the spaces first
-        let can_move: bool = match direction {
-            '>' => self
-                .mask
-                .iter()
-                .map(|(a, b)| ((a + 1), *b))
-                .all(|space| cave.is_space_free(space)),
-            '<' => self
-                .mask
-                .iter()
-                .map(|(a, b)| ((a - 1), *b))
-                .all(|space| cave.is_space_free(space)),
-            _ => panic!("Parsing error"),
-        };
-
-        if can_move {
-            // update the block
-            match direction {
-                '>' => {
-                    self.mask.iter_mut().for_each(|space| {
-                        space.0 += 1;
-                    });
-                }
-                '<' => {
-                    self.mask.iter_mut().for_each(|space| {
-                        space.0 -= 1;
-                    });
-                }
-                _ => panic!("Parsing error"),
-            }
-        }
-
-        can_move
+    fn jet_push(&mut self, direction: &Direction, cave: &Cave) -> bool {
+        self.shift(direction.offset(), cave)
     }
 
     /// returns false if the movement did not occur
     fn fall_down(&mut self, cave: &Cave) -> bool {
+        // the cave grows upwards, so falling lowers y
+        self.shift(Point2::new(0, -1), cave)
+    }
+
+    /// moves every space of the block by `offset` if all the new spaces are free,
+    /// returns false if the movement did not occur
+    fn shift(&mut self, offset: Point2, cave: &Cave) -> bool {
         // check the spaces first
         let can_move: bool = self
             .mask
             .iter()
-            .map(|(a, b)| (*a, b - 1))
-            .all(|space| cave.is_space_free(space));
+            .all(|space| cave.is_space_free(*space + offset));
 
         if can_move {
-            self.mask.iter_mut().for_each(|space| space.1 -= 1)
+            self.mask.iter_mut().for_each(|space| *space += offset)
         }
 
         can_move
@@ -103,134 +166,215 @@ impl Block {
 }
 
 struct Cave {
-    occupied_spaces: HashSet<(isize, isize)>,
+    occupied_spaces: Grid<bool>,
+    /// height of the highest occupied space of every column, -1 if the column is empty
+    column_heights: [isize; CAVE_WIDTH],
     dropped_blocks: usize,
 }
 
 impl Cave {
     fn new() -> Self {
         Self {
-            occupied_spaces: HashSet::new(),
+            occupied_spaces: Grid::sparse(CAVE_WIDTH, 0, false),
+            column_heights: [-1; CAVE_WIDTH],
             dropped_blocks: 0,
         }
     }
 
-    fn print(&self, block: Option<&Block>) {
-        let max_height = self.occupied_spaces.iter().map(|s| s.1).max().unwrap_or(0) + 10;
-        println!("---------------------------");
-        println!("Max height: {}", max_height);
-        println!("Falling block: {:?}", block);
+    /// draws the cave from the top, `#` is rock and `@` the falling block
+    fn render(&self, block: Option<&Block>) -> String {
+        let max_height = self.height() + 10;
+        let mut drawing = String::new();
 
         for y in (0..max_height + 1).rev() {
+            drawing.push('\n');
             for x in 0..7 {
-                match self.occupied_spaces.contains(&(x, y)) {
-                    true => {
-                        print!("#")
-                    }
-                    false => {
-                        if let Some(block) = block {
-                            if block.mask.contains(&(x, y)) {
-                                print!("@")
-                            } else {
-                                print!(".")
-                            }
-                        } else {
-                            print!(".")
-                        }
-                    }
-                }
+                let space = Point2::new(x, y);
+                drawing.push(if !self.is_space_free(space) {
+                    '#'
+                } else if block.is_some_and(|block| block.mask.contains(&space)) {
+                    '@'
+                } else {
+                    '.'
+                });
             }
-            println!()
         }
+
+        drawing
     }
 
-    fn is_space_free(&self, space: (isize, isize)) -> bool {
-        !self.occupied_spaces.contains(&space)
-            && space.0 >= 0
-            && space.0 < CAVE_WIDTH as isize
-            && space.1 >= 0
+    fn is_space_free(&self, space: Point2) -> bool {
+        match space.to_position() {
+            Some(position) if position.0 < CAVE_WIDTH => {
+                self.occupied_spaces.get(position) != Some(&true)
+            }
+            _ => false,
+        }
     }
 
-    fn calc_start_position(&self) -> (isize, isize) {
-        (
-            2,
-            self.occupied_spaces.iter().map(|s| s.1).max().unwrap_or(-1) + 4,
-        )
+    fn calc_start_position(&self) -> Point2 {
+        Point2::new(2, self.height() + 3)
     }
 
     fn fix_block(&mut self, block: &Block) {
-        block.mask.iter().for_each(|offset| {
-            self.occupied_spaces.insert(*offset);
+        block.mask.iter().for_each(|space| {
+            let position = space
+                .to_position()
+                .expect("A fixed block is inside the cave");
+            self.occupied_spaces.insert(position, true);
+            let column_height = &mut self.column_heights[position.0];
+            *column_height = (*column_height).max(space.y);
         });
         self.dropped_blocks += 1;
     }
+
+    fn height(&self) -> isize {
+        self.occupied_spaces.height() as isize
+    }
+
+    /// returns the depth of every column relative to the highest column
+    fn skyline(&self) -> [isize; CAVE_WIDTH] {
+        let height = self.height();
+        self.column_heights
+            .map(|column_height| height - column_height)
+    }
 }
 
-fn main() {
-    let file = File::open("./data/day17.test").expect("file not found!");
-    let movements_vec = io::BufReader::new(file)
-        .lines()
-        .into_iter()
-        .next()
-        .unwrap()
-        .unwrap()
-        .chars()
-        .collect::<Vec<char>>();
-    let mut movements = movements_vec.iter().cycle();
-
-    let h_bar = Block::new(BlockType::HorizontalBar);
-    let v_bar = Block::new(BlockType::VerticalBar);
-    let l_block = Block::new(BlockType::L);
-    let cross = Block::new(BlockType::Cross);
-    let cube = Block::new(BlockType::Cube);
-
-    let mut cave = Cave::new();
-
-    // loop till movements is empty
-
-    'outer_loop: loop {
-        for block in vec![
-            h_bar.clone(),
-            cross.clone(),
-            l_block.clone(),
-            v_bar.clone(),
-            cube.clone(),
-        ]
-        .iter_mut()
-        {
-            block.init_start(&cave);
-            // cave.print(Some(block));
-
-            // loop till block is stuck
-            'block_dropping: loop {
-                if let Some(direction) = movements.next() {
-                    // jet push (take movement and figure out new position)
-                    block.jet_push(direction, &cave);
-                    // cave.print(Some(block));
-
-                    let did_fall = block.fall_down(&cave);
-                    // cave.print(Some(block));
-
-                    if !did_fall {
-                        // update the cave
-                        cave.fix_block(block);
-                        if cave.dropped_blocks == 2022 {
-                            // cave.print(None);
-                            break 'outer_loop;
-                        };
-                        break 'block_dropping;
-                    };
-                } else {
-                    panic!("For some reason, cycle is broken");
-                }
+/// returns the height of the tower after `block_count` blocks stopped falling
+fn tower_height(movements: &[Direction], block_count: usize) -> usize {
+    let mut tower = Tower::new(movements, block_count);
+
+    // (block, movement, skyline) => (dropped blocks, height) at the moment the state was seen
+    let mut seen_states: HashMap<(usize, usize, [isize; CAVE_WIDTH]), (usize, isize)> =
+        HashMap::new();
+    let mut skipped_height = None;
+
+    // loop till enough blocks are dropped
+    while tower.cave.dropped_blocks < block_count {
+        let block_index = tower.cave.dropped_blocks % tower.blocks.len();
+
+        // loop till block is stuck
+        while !tower.push_and_fall() {}
+
+        if skipped_height.is_none() {
+            // the same block falling with the same movements on the same skyline repeats itself
+            let cave = &mut tower.cave;
+            let state = (block_index, tower.jet, cave.skyline());
+
+            if let Some((dropped_blocks, height)) =
+                seen_states.insert(state, (cave.dropped_blocks, cave.height()))
+            {
+                let cycle_length = cave.dropped_blocks - dropped_blocks;
+                let cycles = (block_count - cave.dropped_blocks) / cycle_length;
+
+                debug!(cycle_length, cycles, "skipping repeated blocks");
+                cave.dropped_blocks += cycles * cycle_length;
+                skipped_height = Some(cycles * (cave.height() - height) as usize);
+            }
+        }
+    }
+
+    let cave = &tower.cave;
+    trace!(cave = %cave.render(None), "tower");
+    cave.height() as usize + skipped_height.unwrap_or(0)
+}
+
+/// Blocks falling into the cave, every step a jet pushes the falling block before it falls.
+pub struct Tower {
+    cave: Cave,
+    blocks: [Block; 5],
+    jets: Vec<Direction>,
+    /// index of the next jet
+    jet: usize,
+    /// the falling block, none till the next one appears
+    falling: Option<Block>,
+    /// blocks to drop before the simulation ends
+    block_count: usize,
+}
+
+/// the rows drawn from the top of the cave
+const VIEW_HEIGHT: usize = 30;
+
+impl Tower {
+    pub fn new(jets: &[Direction], block_count: usize) -> Self {
+        Tower {
+            cave: Cave::new(),
+            blocks: [
+                Block::new(BlockType::HorizontalBar),
+                Block::new(BlockType::Cross),
+                Block::new(BlockType::L),
+                Block::new(BlockType::VerticalBar),
+                Block::new(BlockType::Cube),
+            ],
+            jets: jets.to_vec(),
+            jet: 0,
+            falling: None,
+            block_count,
+        }
+    }
+
+    /// lets the next jet push the falling block and lets it fall, the next block appears first
+    /// if none is falling, returns true once the block is stuck
+    fn push_and_fall(&mut self) -> bool {
+        let mut block = match self.falling.take() {
+            Some(block) => block,
+            None => {
+                let mut block = self.blocks[self.cave.dropped_blocks % self.blocks.len()].clone();
+                block.init_start(&self.cave);
+                trace!(?block, cave = %self.cave.render(Some(&block)), "falling block");
+                block
             }
+        };
+
+        // jet push (take movement and figure out new position)
+        let direction = &self.jets[self.jet];
+        self.jet = (self.jet + 1) % self.jets.len();
+        block.jet_push(direction, &self.cave);
+        trace!(?direction, cave = %self.cave.render(Some(&block)), "jet push");
+
+        let did_fall = block.fall_down(&self.cave);
+        trace!(did_fall, cave = %self.cave.render(Some(&block)), "fall");
+
+        if !did_fall {
+            // update the cave
+            self.cave.fix_block(&block);
+            return true;
         }
+
+        self.falling = Some(block);
+        false
     }
+}
 
-    println!("Dropped blocks: {}", cave.dropped_blocks);
+impl Simulation for Tower {
+    fn step(&mut self) -> bool {
+        if self.falling.is_none() && self.cave.dropped_blocks >= self.block_count {
+            return false;
+        }
+        self.push_and_fall();
 
-    println!(
-        "Result: {}",
-        cave.occupied_spaces.iter().map(|s| s.1).max().unwrap() + 1
-    )
+        true
+    }
+}
+
+impl Render for Tower {
+    /// draws the top of the cave between its walls like the puzzle
+    fn render(&self) -> String {
+        let mut drawing = format!(
+            "{} blocks, the tower is {} high\n",
+            self.cave.dropped_blocks,
+            self.cave.height()
+        );
+
+        let rows = self.cave.render(self.falling.as_ref());
+        let rows = rows.lines().skip(1).collect::<Vec<&str>>();
+        for row in rows.iter().take(VIEW_HEIGHT) {
+            drawing.push_str(&format!("|{}|\n", row));
+        }
+        if rows.len() <= VIEW_HEIGHT {
+            drawing.push_str("+-------+\n");
+        }
+
+        drawing
+    }
 }
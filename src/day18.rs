@@ -1,138 +1,123 @@
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{self, BufRead},
-};
+use std::collections::HashSet;
 
 use itertools::Itertools;
 
-#[derive(Debug)]
-struct Cube {
-    position: (isize, isize, isize),
-    // top bottom left right front rear
-    covered_side: Vec<bool>,
-    // top bottom left right front rear
-    free_space: Vec<bool>,
-}
+use crate::{error, geom::Point3, params::Params, ParseError, Solution};
 
-impl Cube {
-    fn mark_top(&mut self, positions: &HashSet<(isize, isize, isize)>) {
-        let position_to_check = (self.position.0 + 1, self.position.1, self.position.2);
-        if positions.iter().any(|p| p == &position_to_check) {
-            self.covered_side[0] = true
-        } else if is_air_covered(&position_to_check, positions) {
-            self.free_space[0] = false
-        }
-    }
+pub struct Day18;
 
-    fn mark_bottom(&mut self, positions: &HashSet<(isize, isize, isize)>) {
-        let position_to_check = (self.position.0 - 1, self.position.1, self.position.2);
-        if positions.iter().any(|p| p == &position_to_check) {
-            self.covered_side[1] = true
-        } else if is_air_covered(&position_to_check, positions) {
-            self.free_space[1] = false
-        }
-    }
+impl Solution for Day18 {
+    const DAY: u8 = 18;
 
-    fn mark_left(&mut self, positions: &HashSet<(isize, isize, isize)>) {
-        let position_to_check = (self.position.0, self.position.1 - 1, self.position.2);
-        if positions.iter().any(|p| p == &position_to_check) {
-            self.covered_side[2] = true
-        } else if is_air_covered(&position_to_check, positions) {
-            self.free_space[2] = false
-        }
+    type Input = Vec<Cube>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        scan_cubes(input)
     }
 
-    fn mark_right(&mut self, positions: &HashSet<(isize, isize, isize)>) {
-        let position_to_check = (self.position.0, self.position.1 + 1, self.position.2);
-        if positions.iter().any(|p| p == &position_to_check) {
-            self.covered_side[3] = true
-        } else if is_air_covered(&position_to_check, positions) {
-            self.free_space[3] = false
-        }
+    fn part1(cubes: &Self::Input, _params: &Params) -> Self::Answer1 {
+        cubes.iter().fold(0, |count, cube| {
+            count + cube.covered_side.iter().filter(|s| !**s).count()
+        })
     }
 
-    fn mark_front(&mut self, positions: &HashSet<(isize, isize, isize)>) {
-        let position_to_check = (self.position.0, self.position.1, self.position.2 - 1);
-        if positions.iter().any(|p| p == &position_to_check) {
-            self.covered_side[4] = true
-        } else if is_air_covered(&position_to_check, positions) {
-            self.free_space[4] = false
-        }
+    fn part2(cubes: &Self::Input, _params: &Params) -> Self::Answer2 {
+        cubes.iter().fold(0, |count, cube| {
+            count
+                + cube
+                    .covered_side
+                    .iter()
+                    .zip(cube.free_space.iter())
+                    .filter(|(covered, free)| !**covered && **free)
+                    .count()
+        })
     }
+}
+
+#[derive(Debug)]
+pub struct Cube {
+    position: Point3,
+    // top bottom right left rear front
+    covered_side: Vec<bool>,
+    // top bottom right left rear front
+    free_space: Vec<bool>,
+}
 
-    fn mark_rear(&mut self, positions: &HashSet<(isize, isize, isize)>) {
-        let position_to_check = (self.position.0, self.position.1, self.position.2 + 1);
-        if positions.iter().any(|p| p == &position_to_check) {
-            self.covered_side[5] = true
-        } else if is_air_covered(&position_to_check, positions) {
-            self.free_space[5] = false
+impl Cube {
+    /// marks the sides touching another cube as covered, and the sides touching trapped air as not free
+    fn mark_sides(&mut self, positions: &HashSet<Point3>, exterior: &HashSet<Point3>) {
+        for (side, position_to_check) in self.position.neighbours6().enumerate() {
+            if positions.contains(&position_to_check) {
+                self.covered_side[side] = true
+            } else if is_air_covered(&position_to_check, exterior) {
+                self.free_space[side] = false
+            }
         }
     }
 }
 
-fn position_neighbours(position: &(isize, isize, isize)) -> Vec<(isize, isize, isize)> {
-    vec![
-        (position.0 + 1, position.1, position.2),
-        (position.0 - 1, position.1, position.2),
-        (position.0, position.1 + 1, position.2),
-        (position.0, position.1 - 1, position.2),
-        (position.0, position.1, position.2 + 1),
-        (position.0, position.1, position.2 - 1),
-    ]
+/// returns true if the air can't reach the outside of the droplet
+fn is_air_covered(air_position: &Point3, exterior: &HashSet<Point3>) -> bool {
+    !exterior.contains(air_position)
 }
 
-fn is_air_covered(
-    air_position: &(isize, isize, isize),
-    positions: &HashSet<(isize, isize, isize)>,
-) -> bool {
-    let mut neighbouring_positions: HashSet<(isize, isize, isize)> = HashSet::new();
-    neighbouring_positions.extend(position_neighbours(air_position));
-    neighbouring_positions.iter().all(|p| positions.contains(p)) || positions.contains(air_position)
-}
+/// returns the air positions reachable from outside the droplet, within a box around the droplet
+fn exterior_air(positions: &HashSet<Point3>) -> HashSet<Point3> {
+    let coordinates = positions.iter().flat_map(|p| [p.x, p.y, p.z]);
+    let min = coordinates.clone().min().unwrap_or(0) - 1;
+    let max = coordinates.max().unwrap_or(0) + 1;
+
+    let corner = Point3::new(min, min, min);
+    let mut exterior = HashSet::from([corner]);
+    let mut to_visit = vec![corner];
+    while let Some(air_position) = to_visit.pop() {
+        for neighbour in air_position.neighbours6() {
+            let in_box = [neighbour.x, neighbour.y, neighbour.z]
+                .iter()
+                .all(|c| (min..=max).contains(c));
+
+            if in_box && !positions.contains(&neighbour) && exterior.insert(neighbour) {
+                to_visit.push(neighbour);
+            }
+        }
+    }
 
-fn main() {
-    let file = File::open("./data/day18.test").expect("file not found!");
-    let input = io::BufReader::new(file)
-        .lines()
-        .flatten()
-        .collect::<Vec<String>>();
+    exterior
+}
 
-    let mut cubes: Vec<Cube> = input
-        .iter()
-        .map(|i| Cube {
-            position: i
+fn scan_cubes(input: &str) -> Result<Vec<Cube>, ParseError> {
+    let mut cubes: Vec<Cube> = error::lines(Day18::DAY, input)
+        .map(|line| {
+            let position = line
+                .text
                 .split(',')
-                .map(|x| x.parse().unwrap())
-                .collect_tuple()
-                .unwrap(),
-            covered_side: vec![false; 6],
-            free_space: vec![true; 6],
+                .map(|x| line.parse(x, "a coordinate"))
+                .collect::<Result<Vec<isize>, ParseError>>()?
+                .into_iter()
+                .collect_tuple::<(isize, isize, isize)>()
+                .map(Point3::from)
+                .ok_or_else(|| line.error(line.text, "a cube like `2,2,2`"))?;
+
+            Ok(Cube {
+                position,
+                covered_side: vec![false; 6],
+                free_space: vec![true; 6],
+            })
         })
-        .collect();
+        .collect::<Result<Vec<Cube>, ParseError>>()?;
 
     let cube_positions = cubes
         .iter()
         .map(|c| c.position)
-        .collect::<HashSet<(isize, isize, isize)>>();
-
-    cubes.iter_mut().for_each(|cube| {
-        cube.mark_top(&cube_positions);
-        cube.mark_bottom(&cube_positions);
-        cube.mark_left(&cube_positions);
-        cube.mark_right(&cube_positions);
-        cube.mark_front(&cube_positions);
-        cube.mark_rear(&cube_positions);
-    });
-
-    let result = cubes.iter().fold(0, |count, cube| {
-        count + cube.covered_side.iter().filter(|s| !**s).count()
-    });
-
-    let part_2 = cubes.iter().fold(0, |count, cube| {
-        count + cube.free_space.iter().filter(|s| **s).count()
-    });
-
-    println!("Result: {}", result);
-    println!("Result 2: {}", part_2);
+        .collect::<HashSet<Point3>>();
+
+    let exterior = exterior_air(&cube_positions);
+
+    cubes
+        .iter_mut()
+        .for_each(|cube| cube.mark_sides(&cube_positions, &exterior));
+
+    Ok(cubes)
 }
@@ -1,115 +1,888 @@
-use std::cmp::Ordering;
-
 use std::{
-    fs::File,
-    io::{self, BufRead},
+    cmp::{Ordering, Reverse},
+    error::Error,
+    fmt,
 };
 
-fn main() {
-    let file = File::open("./data/day2.input").expect("file not found!");
-    let buf_reader = io::BufReader::new(file);
+use itertools::Itertools;
+use serde::Serialize;
 
-    let input: Vec<_> = buf_reader.lines().flatten().collect();
+use crate::{error, error::Line, gen::Rng, params::Params, ParseError, Solution};
 
-    let score: u32 = input
-        .iter()
-        .map(|l| Game::new(l, false))
-        .map(|game| game.score())
-        .sum();
+pub struct Day2;
 
-    println!("Part 1 - score: {}", score);
+impl Solution for Day2 {
+    const DAY: u8 = 2;
 
-    let score2: u32 = input
-        .iter()
-        .map(|l| Game::new(l, true))
-        .map(|game| game.score())
-        .sum();
+    type Input = Vec<Round>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse(&Rules::classic(), input)
+    }
+
+    fn part1(rounds: &Self::Input, _params: &Params) -> Self::Answer1 {
+        let rules = Rules::classic();
+        score(&rules, rounds, &rules.shape_decoding())
+    }
+
+    fn part2(rounds: &Self::Input, _params: &Params) -> Self::Answer2 {
+        let rules = Rules::classic();
+        score(&rules, rounds, &rules.outcome_decoding())
+    }
+}
+
+/// returns the rounds of a strategy guide written with the letters of the rules
+pub fn parse(rules: &Rules, input: &str) -> Result<Vec<Round>, ParseError> {
+    error::lines(Day2::DAY, input)
+        .map(|line| Round::new(rules, line))
+        .collect()
+}
+
+/// a line of the strategy guide, the meaning of the second column depends on the part
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub opponents_hand: Hand,
+    /// the letter of the second column
+    pub instruction: char,
+}
 
-    println!("Part 2 - score: {}", score2);
+impl Round {
+    fn new(rules: &Rules, line: Line) -> Result<Round, ParseError> {
+        let (opponents_hand, instruction) = line.split_once(" ", "two columns like `A Y`")?;
+
+        let opponents_hand = letter(opponents_hand)
+            .and_then(|letter| rules.opponents_hand(letter))
+            .ok_or_else(|| {
+                let letters = rules.shapes.iter().map(|shape| shape.letter);
+                line.error(
+                    opponents_hand,
+                    format!("an opponent's hand {}", either(letters)),
+                )
+            })?;
+        let instruction = letter(instruction)
+            .filter(|letter| rules.instructions().contains(letter))
+            .ok_or_else(|| {
+                line.error(
+                    instruction,
+                    format!("an instruction {}", either(rules.instructions())),
+                )
+            })?;
+
+        Ok(Round {
+            opponents_hand,
+            instruction,
+        })
+    }
+}
+
+/// returns the letter a column is made of
+fn letter(column: &str) -> Option<char> {
+    let mut chars = column.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(letter), None) => Some(letter),
+        _ => None,
+    }
 }
 
-#[derive(PartialEq, Clone)]
-enum Hand {
-    Rock,
-    Scissors,
-    Paper,
+/// returns the letters as `A, B or C`
+fn either(letters: impl IntoIterator<Item = char>) -> String {
+    let letters = letters.into_iter().map(String::from).collect::<Vec<_>>();
+
+    match letters.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, others)) => format!("{} or {}", others.join(", "), last),
+        None => String::new(),
+    }
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match (self, other) {
-            (a, b) if a == b => Some(Ordering::Equal),
-            (Hand::Rock, Hand::Scissors) => Some(Ordering::Greater),
-            (Hand::Paper, Hand::Rock) => Some(Ordering::Greater),
-            (Hand::Scissors, Hand::Paper) => Some(Ordering::Greater),
-            _ => Some(Ordering::Less),
+/// A shape of the rules, the index of its declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hand(usize);
+
+/// The result of a round for the player of the first hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+    /// returns the outcome of the same round for the other player
+    pub fn reverse(self) -> Outcome {
+        match self {
+            Outcome::Lose => Outcome::Win,
+            Outcome::Draw => Outcome::Draw,
+            Outcome::Win => Outcome::Lose,
         }
     }
 }
 
-impl Hand {
-    fn value(&self) -> u32 {
-        match *self {
-            Hand::Rock => 1,
-            Hand::Paper => 2,
-            Hand::Scissors => 3,
+/// A shape which can be played, and how the strategy guide writes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shape {
+    pub name: String,
+    /// added to the score of every round the shape is played in
+    pub score: u32,
+    /// the letter of the shape in the first column
+    pub letter: char,
+    /// the letter of the second column which plays the shape in part 1
+    pub response: char,
+}
+
+impl Shape {
+    pub fn new(name: &str, score: u32, letter: char, response: char) -> Self {
+        Shape {
+            name: name.to_string(),
+            score,
+            letter,
+            response,
         }
     }
 }
 
-struct Game {
-    opponents_hand: Hand,
-    my_hand: Hand,
+/// The rules of a variant of rock paper scissors: its shapes, which shape beats which and
+/// the scores of the outcomes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Vec<Shape>,
+    /// `beats[a][b]` is true when shape `a` beats shape `b`
+    beats: Vec<Vec<bool>>,
+    /// the scores of a lost, drawn and won round
+    outcome_scores: [u32; 3],
+    /// the letters of the second column asking to lose, draw and win in part 2
+    outcome_letters: [char; 3],
 }
 
-impl Game {
-    pub fn new(input_string: &str, generate_hand: bool) -> Game {
-        let convert_hand = |hand| match hand {
-            "A" | "X" => Hand::Rock,
-            "B" | "Y" => Hand::Paper,
-            "C" | "Z" => Hand::Scissors,
-            _ => {
-                panic!("Could not parse hands")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RulesError {
+    /// a round needs a shape to win and one to lose against
+    TooFewShapes,
+    /// two shapes or two outcomes are written with the same letter
+    DuplicateLetter(char),
+    DuplicateShape(String),
+    UnknownShape(String),
+    /// a shape beats itself, or two shapes beat each other
+    Contradiction(String, String),
+    /// neither of two shapes beats the other
+    Undecided(String, String),
+}
+
+impl Rules {
+    /// checks that every two different shapes are decided by exactly one pair of `beats`,
+    /// pairs are the names of the winning and losing shapes
+    pub fn new(
+        shapes: Vec<Shape>,
+        beats: &[(&str, &str)],
+        outcome_scores: [u32; 3],
+        outcome_letters: [char; 3],
+    ) -> Result<Self, RulesError> {
+        if shapes.len() < 2 {
+            return Err(RulesError::TooFewShapes);
+        }
+        for (index, shape) in shapes.iter().enumerate() {
+            let others = &shapes[..index];
+            if others.iter().any(|other| other.name == shape.name) {
+                return Err(RulesError::DuplicateShape(shape.name.clone()));
             }
-        };
+            if others.iter().any(|other| other.letter == shape.letter) {
+                return Err(RulesError::DuplicateLetter(shape.letter));
+            }
+            if others.iter().any(|other| other.response == shape.response) {
+                return Err(RulesError::DuplicateLetter(shape.response));
+            }
+        }
+        for (index, letter) in outcome_letters.iter().enumerate() {
+            if outcome_letters[..index].contains(letter) {
+                return Err(RulesError::DuplicateLetter(*letter));
+            }
+        }
 
-        let generated_hand = |opponent_hand, instruction| -> Option<Hand> {
-            match instruction {
-                "X" => vec![Hand::Rock, Hand::Paper, Hand::Scissors]
-                    .into_iter()
-                    .find(|h| h < opponent_hand),
-                "Y" => vec![Hand::Rock, Hand::Paper, Hand::Scissors]
-                    .into_iter()
-                    .find(|h| h == opponent_hand),
-                "Z" => vec![Hand::Rock, Hand::Paper, Hand::Scissors]
-                    .into_iter()
-                    .find(|h| h > opponent_hand),
-                _ => {
-                    panic!("Could not parse instruction")
+        let position = |name: &str| {
+            shapes
+                .iter()
+                .position(|shape| shape.name == name)
+                .ok_or_else(|| RulesError::UnknownShape(name.to_string()))
+        };
+        let mut table = vec![vec![false; shapes.len()]; shapes.len()];
+        for (winner, loser) in beats {
+            let (a, b) = (position(winner)?, position(loser)?);
+            if a == b || table[b][a] {
+                return Err(RulesError::Contradiction(
+                    winner.to_string(),
+                    loser.to_string(),
+                ));
+            }
+            table[a][b] = true;
+        }
+        for a in 0..shapes.len() {
+            for b in a + 1..shapes.len() {
+                if !table[a][b] && !table[b][a] {
+                    return Err(RulesError::Undecided(
+                        shapes[a].name.clone(),
+                        shapes[b].name.clone(),
+                    ));
                 }
             }
-        };
+        }
+
+        Ok(Rules {
+            shapes,
+            beats: table,
+            outcome_scores,
+            outcome_letters,
+        })
+    }
+
+    /// rock, paper and scissors as in the puzzle
+    pub fn classic() -> Self {
+        Rules::new(
+            vec![
+                Shape::new("rock", 1, 'A', 'X'),
+                Shape::new("paper", 2, 'B', 'Y'),
+                Shape::new("scissors", 3, 'C', 'Z'),
+            ],
+            &[
+                ("rock", "scissors"),
+                ("paper", "rock"),
+                ("scissors", "paper"),
+            ],
+            [0, 3, 6],
+            ['X', 'Y', 'Z'],
+        )
+        .expect("The classic rules are valid")
+    }
+
+    /// rock, paper, scissors, lizard and Spock, the classic shapes keep their letters so
+    /// classic guides are read the same way
+    pub fn lizard_spock() -> Self {
+        Rules::new(
+            vec![
+                Shape::new("rock", 1, 'A', 'X'),
+                Shape::new("paper", 2, 'B', 'Y'),
+                Shape::new("scissors", 3, 'C', 'Z'),
+                Shape::new("lizard", 4, 'D', 'V'),
+                Shape::new("spock", 5, 'E', 'W'),
+            ],
+            &[
+                ("scissors", "paper"),
+                ("paper", "rock"),
+                ("rock", "lizard"),
+                ("lizard", "spock"),
+                ("spock", "scissors"),
+                ("scissors", "lizard"),
+                ("lizard", "paper"),
+                ("paper", "spock"),
+                ("spock", "rock"),
+                ("rock", "scissors"),
+            ],
+            [0, 3, 6],
+            ['X', 'Y', 'Z'],
+        )
+        .expect("The rock paper scissors lizard Spock rules are valid")
+    }
+
+    /// returns every shape, in the order of their declaration
+    pub fn hands(&self) -> impl DoubleEndedIterator<Item = Hand> {
+        (0..self.shapes.len()).map(Hand)
+    }
+
+    pub fn shape(&self, hand: Hand) -> &Shape {
+        &self.shapes[hand.0]
+    }
 
-        let (opponents_hand, my_hand) = input_string.split_once(' ').unwrap();
+    /// returns the result of the round for the player of `a`
+    pub fn outcome(&self, a: Hand, b: Hand) -> Outcome {
+        match (self.beats[a.0][b.0], self.beats[b.0][a.0]) {
+            (true, _) => Outcome::Win,
+            (_, true) => Outcome::Lose,
+            _ => Outcome::Draw,
+        }
+    }
+
+    /// returns the first declared shape which gives the outcome against the opponent
+    pub fn hand_for(&self, opponents_hand: Hand, outcome: Outcome) -> Hand {
+        self.hands()
+            .find(|hand| self.outcome(*hand, opponents_hand) == outcome)
+            .expect("Every pair of shapes is decided")
+    }
+
+    /// returns the score of a round for the player of `hand`
+    pub fn score(&self, hand: Hand, opponents_hand: Hand) -> u32 {
+        let outcome = self.outcome(hand, opponents_hand);
+
+        self.shape(hand).score + self.outcome_scores[outcome as usize]
+    }
+
+    /// returns the decoding of part 1, the letters of the second column play shapes
+    pub fn shape_decoding(&self) -> Decoding {
+        Decoding::Shapes(
+            self.hands()
+                .map(|hand| (self.shape(hand).response, hand))
+                .collect(),
+        )
+    }
+
+    /// returns the decoding of part 2, the letters of the second column ask for outcomes
+    pub fn outcome_decoding(&self) -> Decoding {
+        Decoding::Outcomes(self.outcome_letters.into_iter().zip(Outcome::ALL).collect())
+    }
+
+    fn opponents_hand(&self, letter: char) -> Option<Hand> {
+        self.hands().find(|hand| self.shape(*hand).letter == letter)
+    }
+
+    /// returns the letters of the second column, the letters of the shapes first
+    fn instructions(&self) -> Vec<char> {
+        let mut letters = self
+            .shapes
+            .iter()
+            .map(|shape| shape.response)
+            .collect::<Vec<_>>();
+        for letter in self.outcome_letters {
+            if !letters.contains(&letter) {
+                letters.push(letter);
+            }
+        }
+
+        letters
+    }
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesError::TooFewShapes => write!(f, "the rules need at least two shapes"),
+            RulesError::DuplicateLetter(letter) => {
+                write!(f, "the letter {} has several meanings", letter)
+            }
+            RulesError::DuplicateShape(name) => write!(f, "the shape {} is declared twice", name),
+            RulesError::UnknownShape(name) => write!(f, "the shape {} is not declared", name),
+            RulesError::Contradiction(a, b) if a == b => write!(f, "{} beats itself", a),
+            RulesError::Contradiction(a, b) => {
+                write!(f, "{} and {} beat each other", a, b)
+            }
+            RulesError::Undecided(a, b) => {
+                write!(f, "neither {} nor {} beats the other", a, b)
+            }
+        }
+    }
+}
+
+impl Error for RulesError {}
 
-        let opponents_hand = convert_hand(opponents_hand);
-        let my_hand = match generate_hand {
-            false => convert_hand(my_hand),
-            true => generated_hand(&opponents_hand, my_hand).unwrap(),
+/// returns the total score of the rounds with their second column read with the decoding
+pub fn score(rules: &Rules, rounds: &[Round], decoding: &Decoding) -> u32 {
+    rounds
+        .iter()
+        .map(|round| Game::new(rules, round, decoding))
+        .map(|game| game.score(rules))
+        .sum()
+}
+
+/// How the letters of the second column are read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decoding {
+    /// every letter plays a shape
+    Shapes(Vec<(char, Hand)>),
+    /// every letter asks for an outcome of the round
+    Outcomes(Vec<(char, Outcome)>),
+}
+
+impl Decoding {
+    /// returns every way to read the letters as different shapes, then as different outcomes
+    pub fn all(rules: &Rules, letters: &[char]) -> Vec<Decoding> {
+        let shapes = rules
+            .hands()
+            .permutations(letters.len())
+            .map(|hands| Decoding::Shapes(letters.iter().copied().zip(hands).collect()));
+        let outcomes = Outcome::ALL
+            .into_iter()
+            .permutations(letters.len())
+            .map(|outcomes| Decoding::Outcomes(letters.iter().copied().zip(outcomes).collect()));
+
+        shapes.chain(outcomes).collect()
+    }
+
+    /// returns the hand played against the opponent, `None` if the letter has no meaning
+    pub fn hand(&self, rules: &Rules, opponents_hand: Hand, letter: char) -> Option<Hand> {
+        match self {
+            Decoding::Shapes(shapes) => shapes
+                .iter()
+                .find(|(other, _)| *other == letter)
+                .map(|(_, hand)| *hand),
+            Decoding::Outcomes(outcomes) => outcomes
+                .iter()
+                .find(|(other, _)| *other == letter)
+                .map(|(_, outcome)| rules.hand_for(opponents_hand, *outcome)),
+        }
+    }
+
+    /// returns the decoding as `X=rock Y=paper Z=scissors`
+    pub fn describe(&self, rules: &Rules) -> String {
+        let meanings = match self {
+            Decoding::Shapes(shapes) => shapes
+                .iter()
+                .map(|(letter, hand)| (letter, rules.shape(*hand).name.clone()))
+                .collect::<Vec<_>>(),
+            Decoding::Outcomes(outcomes) => outcomes
+                .iter()
+                .map(|(letter, outcome)| (letter, format!("{:?}", outcome).to_lowercase()))
+                .collect(),
         };
 
+        meanings
+            .iter()
+            .map(|(letter, meaning)| format!("{}={}", letter, meaning))
+            .join(" ")
+    }
+}
+
+/// A round with both hands known.
+pub struct Game {
+    pub opponents_hand: Hand,
+    pub my_hand: Hand,
+}
+
+impl Game {
+    /// plays the hand the decoding reads in the second column, which should give every
+    /// letter of the guide a meaning
+    pub fn new(rules: &Rules, round: &Round, decoding: &Decoding) -> Game {
+        let my_hand = decoding
+            .hand(rules, round.opponents_hand, round.instruction)
+            .expect("The decoding reads every letter of the guide");
+
         Game {
-            opponents_hand,
+            opponents_hand: round.opponents_hand,
             my_hand,
         }
     }
 
-    pub fn score(&self) -> u32 {
-        match (&self.opponents_hand, &self.my_hand) {
-            (a, b) if a > b => b.value(),
-            (a, b) if a == b => b.value() + 3,
-            (a, b) if b > a => b.value() + 6,
-            _ => panic!("Big mistakes were made"),
+    pub fn score(&self, rules: &Rules) -> u32 {
+        rules.score(self.my_hand, self.opponents_hand)
+    }
+}
+
+/// The points of a round of the guide read with a decoding.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RoundScore {
+    /// 1-based position of the round in the guide
+    pub round: usize,
+    /// the two columns of the round like `A Y`
+    pub columns: String,
+    pub played: String,
+    /// points for the shape played
+    pub shape: u32,
+    /// points for the outcome of the round
+    pub outcome: u32,
+    pub total: u32,
+}
+
+/// A decoding of the second column and the score of the guide read with it.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Interpretation {
+    pub decoding: String,
+    pub score: u32,
+    /// the rounds in the order of the guide
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rounds: Vec<RoundScore>,
+}
+
+/// Every reading of a strategy guide, which tells the decoding the guide was made for.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    /// the decodings ranked by score, highest first
+    pub interpretations: Vec<Interpretation>,
+    pub best: Interpretation,
+    pub worst: Interpretation,
+}
+
+/// returns the scores of the guide read with every decoding of the letters of its second
+/// column, the `top` decodings scoring the most are listed, and the first `shown_rounds`
+/// rounds of the best and the worst decodings are broken down
+pub fn report(rules: &Rules, rounds: &[Round], top: usize, shown_rounds: usize) -> Report {
+    let letters = rounds
+        .iter()
+        .map(|round| round.instruction)
+        .sorted()
+        .dedup()
+        .collect::<Vec<_>>();
+    let decodings = Decoding::all(rules, &letters);
+
+    // ties go to the decoding enumerated first, shapes before outcomes
+    let scores = decodings
+        .iter()
+        .map(|decoding| score(rules, rounds, decoding))
+        .collect::<Vec<_>>();
+    let best = (0..decodings.len()).rev().max_by_key(|i| scores[*i]);
+    let worst = (0..decodings.len()).min_by_key(|i| scores[*i]);
+    let detailed = |index: usize| Interpretation {
+        decoding: decodings[index].describe(rules),
+        score: scores[index],
+        rounds: breakdown(
+            rules,
+            &rounds[..shown_rounds.min(rounds.len())],
+            &decodings[index],
+        ),
+    };
+
+    Report {
+        interpretations: (0..decodings.len())
+            .sorted_by_key(|i| Reverse(scores[*i]))
+            .take(top)
+            .map(|i| Interpretation {
+                decoding: decodings[i].describe(rules),
+                score: scores[i],
+                rounds: vec![],
+            })
+            .collect(),
+        best: detailed(best.expect("There is at least one decoding")),
+        worst: detailed(worst.expect("There is at least one decoding")),
+    }
+}
+
+/// returns the points of every round
+fn breakdown(rules: &Rules, rounds: &[Round], decoding: &Decoding) -> Vec<RoundScore> {
+    rounds
+        .iter()
+        .enumerate()
+        .map(|(index, round)| {
+            let game = Game::new(rules, round, decoding);
+            let shape = rules.shape(game.my_hand).score;
+            let total = game.score(rules);
+
+            RoundScore {
+                round: index + 1,
+                columns: format!(
+                    "{} {}",
+                    rules.shape(round.opponents_hand).letter,
+                    round.instruction
+                ),
+                played: rules.shape(game.my_hand).name.clone(),
+                shape,
+                outcome: total - shape,
+                total,
+            }
+        })
+        .collect()
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<40}  {:>8}", "decoding", "score")?;
+        for interpretation in &self.interpretations {
+            writeln!(
+                f,
+                "{:<40}  {:>8}",
+                interpretation.decoding, interpretation.score
+            )?;
+        }
+
+        for (name, interpretation) in [("best", &self.best), ("worst", &self.worst)] {
+            writeln!(
+                f,
+                "\n{}: {} scores {}",
+                name, interpretation.decoding, interpretation.score
+            )?;
+            writeln!(
+                f,
+                "{:>6}  {:>7}  {:<10}  {:>5}  {:>7}  {:>5}",
+                "round", "columns", "played", "shape", "outcome", "total"
+            )?;
+            for round in &interpretation.rounds {
+                writeln!(
+                    f,
+                    "{:>6}  {:>7}  {:<10}  {:>5}  {:>7}  {:>5}",
+                    round.round,
+                    round.columns,
+                    round.played,
+                    round.shape,
+                    round.outcome,
+                    round.total
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A player of repeated rounds against the same opponent, which chooses its hands from the
+/// rounds played so far.
+pub trait Player {
+    fn name(&self) -> String;
+
+    /// returns the hand of the next round
+    fn play(&mut self, rules: &Rules) -> Hand;
+
+    /// learns the hands of the round which was just played
+    fn observe(&mut self, rules: &Rules, mine: Hand, theirs: Hand);
+
+    /// forgets the rounds played, before meeting a new opponent
+    fn reset(&mut self);
+}
+
+/// Always plays the same hand.
+pub struct Fixed {
+    hand: Hand,
+    name: String,
+}
+
+impl Fixed {
+    pub fn new(rules: &Rules, hand: Hand) -> Self {
+        Fixed {
+            hand,
+            name: format!("always {}", rules.shape(hand).name),
+        }
+    }
+}
+
+impl Player for Fixed {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn play(&mut self, _rules: &Rules) -> Hand {
+        self.hand
+    }
+
+    fn observe(&mut self, _rules: &Rules, _mine: Hand, _theirs: Hand) {}
+
+    fn reset(&mut self) {}
+}
+
+/// Plays random hands, the same ones against every opponent.
+pub struct Random {
+    seed: u64,
+    rng: Rng,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Random {
+            seed,
+            rng: Rng::new(seed),
+        }
+    }
+}
+
+impl Player for Random {
+    fn name(&self) -> String {
+        format!("random {}", self.seed)
+    }
+
+    fn play(&mut self, rules: &Rules) -> Hand {
+        Hand(self.rng.below(rules.shapes.len()))
+    }
+
+    fn observe(&mut self, _rules: &Rules, _mine: Hand, _theirs: Hand) {}
+
+    fn reset(&mut self) {
+        self.rng = Rng::new(self.seed);
+    }
+}
+
+/// Plays the hand beating the hand the opponent played the most, the first declared one
+/// on ties.
+#[derive(Default)]
+pub struct FrequencyCounter {
+    counts: Vec<usize>,
+}
+
+impl Player for FrequencyCounter {
+    fn name(&self) -> String {
+        "frequency counter".to_string()
+    }
+
+    fn play(&mut self, rules: &Rules) -> Hand {
+        let most_played = rules
+            .hands()
+            .rev()
+            .max_by_key(|hand| self.counts.get(hand.0).copied().unwrap_or(0))
+            .expect("The rules have shapes");
+
+        rules.hand_for(most_played, Outcome::Win)
+    }
+
+    fn observe(&mut self, rules: &Rules, _mine: Hand, theirs: Hand) {
+        self.counts.resize(rules.shapes.len(), 0);
+        self.counts[theirs.0] += 1;
+    }
+
+    fn reset(&mut self) {
+        self.counts.clear();
+    }
+}
+
+/// Plays its hand again after a win, otherwise the hand which would have beaten the
+/// opponent's last one.
+#[derive(Default)]
+pub struct WinStayLoseShift {
+    last: Option<(Hand, Hand)>,
+}
+
+impl Player for WinStayLoseShift {
+    fn name(&self) -> String {
+        "win-stay lose-shift".to_string()
+    }
+
+    fn play(&mut self, rules: &Rules) -> Hand {
+        match self.last {
+            None => Hand(0),
+            Some((mine, theirs)) if rules.outcome(mine, theirs) == Outcome::Win => mine,
+            Some((_, theirs)) => rules.hand_for(theirs, Outcome::Win),
+        }
+    }
+
+    fn observe(&mut self, _rules: &Rules, mine: Hand, theirs: Hand) {
+        self.last = Some((mine, theirs));
+    }
+
+    fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+/// Plays the hands of a strategy guide over and over, as if the opponent played the hands
+/// of its first column.
+pub struct FollowTheGuide {
+    hands: Vec<Hand>,
+    next: usize,
+}
+
+impl FollowTheGuide {
+    /// reads the second column of the guide with the decoding, `None` if it can not read one
+    /// of its letters, an empty guide plays the first declared shape
+    pub fn new(rules: &Rules, rounds: &[Round], decoding: &Decoding) -> Option<Self> {
+        let hands = rounds
+            .iter()
+            .map(|round| decoding.hand(rules, round.opponents_hand, round.instruction))
+            .collect::<Option<_>>()?;
+
+        Some(FollowTheGuide { hands, next: 0 })
+    }
+}
+
+impl Player for FollowTheGuide {
+    fn name(&self) -> String {
+        "follow the guide".to_string()
+    }
+
+    fn play(&mut self, _rules: &Rules) -> Hand {
+        let hand = self.hands.get(self.next).copied().unwrap_or(Hand(0));
+        self.next = (self.next + 1) % self.hands.len().max(1);
+
+        hand
+    }
+
+    fn observe(&mut self, _rules: &Rules, _mine: Hand, _theirs: Hand) {}
+
+    fn reset(&mut self) {
+        self.next = 0;
+    }
+}
+
+/// The results of a player over the matches of a tournament.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    /// players with the same wins and score share their rank
+    pub rank: usize,
+    pub player: String,
+    /// matches won, drawn and lost, a match is won with the higher score over its rounds
+    pub won: usize,
+    pub drawn: usize,
+    pub lost: usize,
+    /// the score of every round played, as in the puzzle
+    pub score: u32,
+}
+
+/// The standings of a round-robin tournament.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Standings {
+    /// rounds of every match
+    pub rounds: usize,
+    /// the players with the most wins first, then the highest scores
+    pub standings: Vec<Standing>,
+}
+
+/// plays a match of `rounds` rounds between every two players
+pub fn tournament(rules: &Rules, players: &mut [Box<dyn Player>], rounds: usize) -> Standings {
+    let mut standings = players
+        .iter()
+        .map(|player| Standing {
+            rank: 0,
+            player: player.name(),
+            won: 0,
+            drawn: 0,
+            lost: 0,
+            score: 0,
+        })
+        .collect::<Vec<_>>();
+
+    for (a, b) in (0..players.len()).tuple_combinations() {
+        let (first, second) = players.split_at_mut(b);
+        let (player_a, player_b) = (&mut first[a], &mut second[0]);
+        player_a.reset();
+        player_b.reset();
+
+        let (mut score_a, mut score_b) = (0, 0);
+        for _ in 0..rounds {
+            let (hand_a, hand_b) = (player_a.play(rules), player_b.play(rules));
+            player_a.observe(rules, hand_a, hand_b);
+            player_b.observe(rules, hand_b, hand_a);
+            score_a += rules.score(hand_a, hand_b);
+            score_b += rules.score(hand_b, hand_a);
+        }
+
+        standings[a].score += score_a;
+        standings[b].score += score_b;
+        match score_a.cmp(&score_b) {
+            Ordering::Greater => (standings[a].won += 1, standings[b].lost += 1),
+            Ordering::Equal => (standings[a].drawn += 1, standings[b].drawn += 1),
+            Ordering::Less => (standings[a].lost += 1, standings[b].won += 1),
+        };
+    }
+
+    standings.sort_by(|a, b| b.won.cmp(&a.won).then(b.score.cmp(&a.score)));
+    for position in 0..standings.len() {
+        standings[position].rank = match position.checked_sub(1).map(|i| &standings[i]) {
+            Some(previous)
+                if (previous.won, previous.score)
+                    == (standings[position].won, standings[position].score) =>
+            {
+                previous.rank
+            }
+            _ => position + 1,
+        };
+    }
+
+    Standings { rounds, standings }
+}
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>4}  {:<20}  {:>4}  {:>5}  {:>4}  {:>10}",
+            "rank", "player", "won", "drawn", "lost", "score"
+        )?;
+        for standing in &self.standings {
+            writeln!(
+                f,
+                "{:>4}  {:<20}  {:>4}  {:>5}  {:>4}  {:>10}",
+                standing.rank,
+                standing.player,
+                standing.won,
+                standing.drawn,
+                standing.lost,
+                standing.score
+            )?;
         }
+        writeln!(f, "every match lasted {} rounds", self.rounds)
     }
 }
@@ -1,59 +1,116 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
+use crate::{error, params::Params, ParseError, Solution};
 
-fn main() {
-    let alphabet: Vec<char> = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
-        .chars()
-        .collect();
+pub struct Day3;
 
-    let calculate_score_for_char = |c: &char| {
-        alphabet
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    /// contents of every rucksack
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let lines = error::lines(Self::DAY, input).collect::<Vec<_>>();
+        let rucksacks = lines
             .iter()
-            .enumerate()
-            .find(|alphabet_char| *alphabet_char.1 == *c)
-            .map_or_else(|| panic!("No score found for character {}", c), |found| found.0 + 1)
-    };
-
-    let find_overlapping_char = |vectors: &Vec<String>| {
-        match vectors.len() {
-            0 | 1 => {panic!("At least 2 strings should be passed")},
-            _ => {}
-        }
+            .map(|line| {
+                if line.text.is_empty() {
+                    return Err(line.error(line.text, "a rucksack with items"));
+                }
+                if let Some((index, _)) = line
+                    .text
+                    .char_indices()
+                    .find(|(_, c)| !c.is_ascii_alphabetic())
+                {
+                    return Err(line.error(&line.text[index..], "only item letters a-z and A-Z"));
+                }
+                if line.text.len() % 2 != 0 {
+                    return Err(line.error(
+                        line.text,
+                        "an even amount of items to fill both compartments",
+                    ));
+                }
+                let (first, second) = line.text.split_at(line.text.len() / 2);
+                if shared_item(&[first, second]).is_none() {
+                    return Err(line.error(line.text, "an item in both compartments"));
+                }
 
-        vectors[0]
-            .chars()
-            .find(|c| vectors.iter().skip(1).all(|v| v.contains(*c)))
-            .unwrap_or_else(|| panic!("No overlapping character found for {:?}", vectors))
-    };
+                Ok(line.text.to_string())
+            })
+            .collect::<Result<Vec<String>, ParseError>>()?;
 
-    #[allow(clippy::expect_used)]
-    let file = File::open("./data/day3.input").expect("file not found!");
+        // the elves are split into groups of three, which carry the same badge
+        for group in lines.chunks(3) {
+            let last = group[group.len() - 1];
+            if group.len() < 3 {
+                return Err(last.error(last.text, "complete groups of three rucksacks"));
+            }
+            if shared_item(&group.iter().map(|line| line.text).collect::<Vec<_>>()).is_none() {
+                return Err(last.error(
+                    last.text,
+                    "a badge item in all three rucksacks of the group",
+                ));
+            }
+        }
 
-    let buf_reader = io::BufReader::new(file);
-    let input = buf_reader.lines().flatten().collect::<Vec<String>>();
+        Ok(rucksacks)
+    }
 
-    // Part 1
-    let rucksacks_input: Vec<(&str, &str)> =
-        input.iter().map(|s| s.split_at(s.len() / 2)).collect();
+    fn part1(rucksacks: &Self::Input, _params: &Params) -> Self::Answer1 {
+        let rucksacks_input: Vec<(&str, &str)> =
+            rucksacks.iter().map(|s| s.split_at(s.len() / 2)).collect();
 
-    let score: usize = rucksacks_input
-        .iter()
-        .map(|(a, b)| find_overlapping_char(&vec![(*a).to_string(), (*b).to_string()]))
-        .map(|c| calculate_score_for_char(&c))
-        .sum();
+        rucksacks_input
+            .iter()
+            .map(|(a, b)| find_overlapping_char(&[(*a).to_string(), (*b).to_string()]))
+            .map(|c| calculate_score_for_char(&c))
+            .sum()
+    }
 
-    println!("Part 1: {:?}", score);
+    fn part2(rucksacks: &Self::Input, _params: &Params) -> Self::Answer2 {
+        let chunks: Vec<Vec<String>> = rucksacks.chunks(3).map(std::convert::Into::into).collect();
+
+        chunks
+            .iter()
+            .map(|elves| find_overlapping_char(elves))
+            .map(|c| calculate_score_for_char(&c))
+            .sum()
+    }
+}
 
-    // Part 2
-    let chunks: Vec<Vec<String>> = input.chunks(3).map(std::convert::Into::into).collect();
+fn calculate_score_for_char(c: &char) -> usize {
+    let alphabet: Vec<char> = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ"
+        .chars()
+        .collect();
 
-    let badge_score: usize = chunks
+    alphabet
         .iter()
-        .map(|elves| find_overlapping_char(elves))
-        .map(|c| calculate_score_for_char(&c))
-        .sum();
+        .enumerate()
+        .find(|alphabet_char| *alphabet_char.1 == *c)
+        .map_or_else(
+            || panic!("No score found for character {}", c),
+            |found| found.0 + 1,
+        )
+}
 
-    println!("Part 2: {:?}", badge_score);
+/// returns the first item of the first rucksack which is in every rucksack
+fn shared_item(rucksacks: &[&str]) -> Option<char> {
+    rucksacks[0]
+        .chars()
+        .find(|c| rucksacks[1..].iter().all(|rucksack| rucksack.contains(*c)))
+}
+
+fn find_overlapping_char(vectors: &[String]) -> char {
+    match vectors.len() {
+        0 | 1 => {
+            panic!("At least 2 strings should be passed")
+        }
+        _ => {}
+    }
+
+    vectors[0]
+        .chars()
+        .find(|c| vectors.iter().skip(1).all(|v| v.contains(*c)))
+        .unwrap_or_else(|| panic!("No overlapping character found for {:?}", vectors))
 }
@@ -1,34 +1,57 @@
 use std::collections::HashSet;
-use std::{
-    fs::File,
-    io::{self, BufRead},
-};
 
-fn main() {
-    let file = File::open("./data/day4.input").expect("file not found!");
-
-    let buf_reader = io::BufReader::new(file);
-    let input = buf_reader.lines().flatten().collect::<Vec<String>>();
-
-    let parse_assignments = |assignment: &str| {
-      let (a_start_string, a_end_string) = assignment.split_once('-').unwrap_or_else(|| panic!("Could not parse assignment"));
-
-      (a_start_string.parse::<u32>().unwrap()..a_end_string.parse::<u32>().unwrap()+1)
-          .collect::<HashSet<_>>()
-    };
-
-
-    let overlapping = input.iter()
-         .map(|s| s.split_once(',').unwrap_or_else(|| panic!("Could not find assignments")))
-         .filter(|(a,b)| {
-             let assignment_a = parse_assignments(a);
-             let assignment_b = parse_assignments(b);
+use crate::{error, error::Line, params::Params, ParseError, Solution};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+
+    /// section assignments of every pair of elves
+    type Input = Vec<(HashSet<u32>, HashSet<u32>)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        error::lines(Self::DAY, input)
+            .map(|line| {
+                let (a, b) = line.split_once(",", "two assignments like `2-4,6-8`")?;
+                Ok((parse_assignments(&line, a)?, parse_assignments(&line, b)?))
+            })
+            .collect()
+    }
+
+    fn part1(pairs: &Self::Input, _params: &Params) -> Self::Answer1 {
+        pairs
+            .iter()
+            .filter(|(assignment_a, assignment_b)| {
+                assignment_a.iter().all(|item| assignment_b.contains(item))
+                    || assignment_b.iter().all(|item| assignment_a.contains(item))
+            })
+            .count()
+    }
+
+    fn part2(pairs: &Self::Input, _params: &Params) -> Self::Answer2 {
+        pairs
+            .iter()
+            .filter(|(assignment_a, assignment_b)| {
+                assignment_a.iter().any(|item| assignment_b.contains(item))
+                    || assignment_b.iter().any(|item| assignment_a.contains(item))
+            })
+            .count()
+    }
+}
 
-             // PART 1 : all
-             // PART 2 : any
-             assignment_a.iter().any(|item| assignment_b.contains(item)) || assignment_b.iter().any(|item| assignment_a.contains(item))
-         }).count();
+fn parse_assignments(line: &Line, assignment: &str) -> Result<HashSet<u32>, ParseError> {
+    let (a_start_string, a_end_string) = assignment
+        .split_once('-')
+        .ok_or_else(|| line.error(assignment, "a section range like `2-4`"))?;
 
-    println!("Overlapping: {}", overlapping);
+    let start = line.parse::<u32>(a_start_string, "a section number")?;
+    let end = line.parse::<u32>(a_end_string, "a section number")?;
+    let after_end = end
+        .checked_add(1)
+        .ok_or_else(|| line.error(a_end_string, format!("a section number below {}", u32::MAX)))?;
 
+    Ok((start..after_end).collect::<HashSet<_>>())
 }
@@ -1,73 +1,124 @@
-use std::{
-    fs::File,
-    io::{self, BufRead},
-    num::ParseIntError,
-    str::FromStr,
-};
-
-fn main() {
-    let file = File::open("./data/day5.input").expect("file not found!");
-
-    let buf_reader = io::BufReader::new(file);
-    let input = buf_reader.lines().flatten().collect::<Vec<String>>();
-    let mut input_split = input.split(std::string::String::is_empty);
-
-    #[allow(clippy::unwrap_used)]
-    let (mut stack, moves_input) = (
-        input_split.next().unwrap().to_vec(),
-        input_split.next().unwrap().to_vec(),
-    );
-
-    #[allow(clippy::unwrap_used)]
-    let indices: Vec<usize> = stack
-        .pop()
-        .unwrap()
-        .chars()
-        .enumerate()
-        .filter_map(|(i, c)| if c.is_numeric() { Some(i) } else { None })
-        .collect();
-
-    let mut stacks = indices
-        .iter()
-        .map(|i| {
-            let containers = stack
-                .iter()
-                .filter_map(|s| {
-                    let chars = s.chars().collect::<Vec<char>>();
-                    let container = chars[*i];
-                    if container == ' ' {
-                        None
-                    } else {
-                        Some(container)
-                    }
-                })
-                .rev()
-                .collect::<Vec<char>>();
-            containers
-        })
-        .collect::<Vec<Vec<char>>>();
-
-    let moves: Vec<Move> = moves_input
-        .iter()
-        .map(|s| Move::from_str(s).unwrap_or_else(|_| panic!("Could not parse move")))
-        .collect();
-
-    for move_action in &moves {
-        let n = stacks[move_action.from].len();
-        let containers_to_move: Vec<char> = stacks[move_action.from]
-            .drain(n - move_action.amount..)
-            .rev() // remove for PART 2
+use std::str::FromStr;
+
+use crate::{error, error::Line, params::Params, ParseError, Solution};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = Cargo;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Cargo::new(input)
+    }
+
+    fn part1(cargo: &Self::Input, _params: &Params) -> Self::Answer1 {
+        cargo.rearrange(true)
+    }
+
+    fn part2(cargo: &Self::Input, _params: &Params) -> Self::Answer2 {
+        cargo.rearrange(false)
+    }
+}
+
+/// the starting stacks of containers and the moves of the crane
+pub struct Cargo {
+    stacks: Vec<Vec<char>>,
+    moves: Vec<Move>,
+}
+
+impl Cargo {
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let input = error::lines(Day5::DAY, input).collect::<Vec<Line>>();
+        let mut input_split = input.split(|line| line.text.is_empty());
+
+        let (mut stack, moves_input) = match (input_split.next(), input_split.next()) {
+            (Some(stack), Some(moves_input)) => (stack.to_vec(), moves_input.to_vec()),
+            _ => {
+                return Err(ParseError::new(
+                    Day5::DAY,
+                    "",
+                    "a blank line between the stacks and the moves",
+                )
+                .below(input.len()))
+            }
+        };
+
+        let Some(numbers_line) = stack.pop() else {
+            return Err(ParseError::new(Day5::DAY, "", "a drawing of the stacks"));
+        };
+        let indices: Vec<usize> = numbers_line
+            .text
+            .chars()
+            .enumerate()
+            .filter_map(|(i, c)| if c.is_numeric() { Some(i) } else { None })
             .collect();
 
-        stacks[move_action.to].extend(containers_to_move);
+        let stacks = indices
+            .iter()
+            .map(|i| {
+                let containers = stack
+                    .iter()
+                    .filter_map(|line| match line.text.get(*i..*i + 1) {
+                        None | Some(" ") => None,
+                        Some(container) => Some(
+                            container
+                                .chars()
+                                .next()
+                                .filter(char::is_ascii_alphabetic)
+                                .ok_or_else(|| line.error(container, "a container letter")),
+                        ),
+                    })
+                    .rev()
+                    .collect::<Result<Vec<char>, ParseError>>();
+                containers
+            })
+            .collect::<Result<Vec<Vec<char>>, ParseError>>()?;
+
+        let moves: Vec<Move> = moves_input
+            .iter()
+            .map(|line| {
+                let move_action =
+                    Move::from_str(line.text).map_err(|e| e.below(line.number - 1))?;
+
+                if move_action.from >= stacks.len() || move_action.to >= stacks.len() {
+                    return Err(
+                        line.error(line.text, format!("stacks between 1 and {}", stacks.len()))
+                    );
+                }
+                Ok(move_action)
+            })
+            .collect::<Result<Vec<Move>, ParseError>>()?;
+
+        Ok(Cargo { stacks, moves })
     }
 
-    let result = stacks
-        .iter()
-        .filter_map(|s| s.iter().last())
-        .collect::<String>();
+    /// moves the containers and returns the containers on top of each stack,
+    /// `one_at_a_time` reverses the order of the moved containers
+    fn rearrange(&self, one_at_a_time: bool) -> String {
+        let mut stacks = self.stacks.clone();
 
-    println!("Result: {:?}", result);
+        for move_action in &self.moves {
+            let n = stacks[move_action.from].len();
+            let mut containers_to_move: Vec<char> = stacks[move_action.from]
+                .drain(n - move_action.amount..)
+                .collect();
+
+            if one_at_a_time {
+                containers_to_move.reverse();
+            }
+
+            stacks[move_action.to].extend(containers_to_move);
+        }
+
+        stacks
+            .iter()
+            .filter_map(|s| s.iter().last())
+            .collect::<String>()
+    }
 }
 
 #[derive(Debug)]
@@ -78,18 +129,26 @@ struct Move {
 }
 
 impl FromStr for Move {
-    type Err = ParseIntError;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut numerics = s
-            .split(' ')
-            .filter_map(|string_split| string_split.parse::<usize>().ok());
+        let line = Line::single(Day5::DAY, s);
+        let expected = "a move like `move 1 from 2 to 1`";
+
+        let ["move", amount, "from", from, "to", to] = s.split(' ').collect::<Vec<&str>>()[..]
+        else {
+            return Err(line.error(s, expected));
+        };
+
+        let parse_stack = |stack| match line.parse::<usize>(stack, "a stack number")? {
+            0 => Err(line.error(stack, "a stack number starting from 1")),
+            stack_number => Ok(stack_number - 1),
+        };
 
-        #[allow(clippy::unwrap_used)]
         Ok(Self {
-            amount: numerics.next().unwrap(),
-            from: numerics.next().unwrap() - 1,
-            to: numerics.next().unwrap() - 1,
+            amount: line.parse(amount, "an amount of containers")?,
+            from: parse_stack(from)?,
+            to: parse_stack(to)?,
         })
     }
 }
@@ -1,31 +1,67 @@
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{self, BufRead},
+use std::collections::HashSet;
+
+use crate::{
+    params::{Parameter, Params},
+    Maybe, ParseError, Solution,
 };
 
-const SIZE: usize = 14;
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+
+    /// the datastream buffer
+    type Input = Vec<char>;
+    type Answer1 = Maybe<usize>;
+    type Answer2 = Maybe<usize>;
+
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter {
+            name: "packet_marker_size",
+            default: 4,
+            min: 1,
+            max: 26,
+            description: "distinct characters of a start-of-packet marker",
+        },
+        Parameter {
+            name: "message_marker_size",
+            default: 14,
+            min: 1,
+            max: 26,
+            description: "distinct characters of a start-of-message marker",
+        },
+    ];
 
-fn main() {
-    let file = File::open("./data/day6.input").expect("file not found!");
-    let input = &(io::BufReader::new(file)
-        .lines()
-        .flatten()
-        .collect::<Vec<String>>())[0];
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        match input.lines().next() {
+            Some(datastream) if !datastream.is_empty() => Ok(datastream.chars().collect()),
+            _ => Err(ParseError::new(Self::DAY, "", "a datastream buffer")),
+        }
+    }
 
-    let characters = input.chars().collect::<Vec<char>>();
-    let (result, _window) = characters
-        .windows(SIZE)
+    fn part1(characters: &Self::Input, params: &Params) -> Self::Answer1 {
+        find_marker(characters, params.get("packet_marker_size"))
+            .map_or(Maybe::Missing("no marker"), Maybe::Found)
+    }
+
+    fn part2(characters: &Self::Input, params: &Params) -> Self::Answer2 {
+        find_marker(characters, params.get("message_marker_size"))
+            .map_or(Maybe::Missing("no marker"), Maybe::Found)
+    }
+}
+
+/// returns the amount of characters processed before the first marker of `size` distinct characters,
+/// none if the datastream has no such marker
+fn find_marker(characters: &[char], size: usize) -> Option<usize> {
+    characters
+        .windows(size)
         .enumerate()
         .find(|(_index, win)| {
             let subwindow = *win;
             let mut collection: HashSet<char> = HashSet::new();
             collection.extend(subwindow);
 
-            //collection.len() == 4
-            collection.len() == SIZE
+            collection.len() == size
         })
-        .unwrap();
-
-    println!("Result {}", result + SIZE);
+        .map(|(result, _window)| result + size)
 }
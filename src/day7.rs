@@ -1,109 +1,183 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{self, BufRead},
+use std::collections::{HashMap, HashSet};
+
+use tracing::{debug, trace};
+
+use crate::{
+    error::{self, Line},
+    params::{Parameter, Params},
+    ParseError, Solution,
 };
 
-use regex::Regex;
+pub struct Day7;
 
-fn main() {
-    let file = File::open("./data/day7.input").expect("file not found!");
-    let input = io::BufReader::new(file)
-        .lines()
-        .flatten()
-        .collect::<Vec<String>>();
+impl Solution for Day7 {
+    const DAY: u8 = 7;
 
-    let mut directories: HashMap<String, usize> = HashMap::new();
+    /// total size of every directory, keyed by its path
+    type Input = HashMap<String, usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    let file_regex = Regex::new(r"([0-9]+) ([\w\.]+)$").unwrap();
-    let dir_regex = Regex::new(r"dir (\w+)$").unwrap();
-    let ls_regex = Regex::new(r"\$ ls").unwrap();
-    let cd_regex = Regex::new(r"\$ cd ([\w/]+)$").unwrap();
-    let cd_back_regex = Regex::new(r"\$ cd \.\.$").unwrap();
-
-    let mut location_helper: Vec<String> = Vec::new();
-    let mut location_history: Vec<Vec<String>> = input
-        .iter()
-        .filter(|l| cd_regex.is_match(l) || cd_back_regex.is_match(l))
-        .map(|l| {
-            if cd_regex.is_match(l) {
-                let dir = &cd_regex.captures(l).unwrap()[1];
-                match dir {
-                    "/" => {
-                        location_helper.push(String::new());
-                    }
-                    _ => {
-                        location_helper.push(dir.to_string());
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter {
+            name: "small_directory_size",
+            default: 100_000,
+            min: 0,
+            max: i64::MAX,
+            description: "size below which a directory counts as small",
+        },
+        Parameter {
+            name: "total_space",
+            default: 70_000_000,
+            min: 0,
+            max: i64::MAX,
+            description: "size of the disk",
+        },
+        Parameter {
+            name: "required_space",
+            default: 30_000_000,
+            min: 0,
+            max: i64::MAX,
+            description: "free space needed for the update",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        directory_sizes(input)
+    }
+
+    fn part1(directories: &Self::Input, params: &Params) -> Self::Answer1 {
+        let small_directory_size = params.get::<usize>("small_directory_size");
+        let small_dirs = directories
+            .iter()
+            .filter(|d| *d.1 < small_directory_size)
+            .map(|d| d.0.to_string())
+            .collect::<Vec<String>>();
+
+        small_dirs.iter().map(|d| directories[d]).sum()
+    }
+
+    fn part2(directories: &Self::Input, params: &Params) -> Self::Answer2 {
+        let used_space = directories[""];
+        let free_space = params
+            .get::<usize>("total_space")
+            .saturating_sub(used_space);
+        let space_to_delete = params
+            .get::<usize>("required_space")
+            .saturating_sub(free_space);
+        if space_to_delete == 0 {
+            debug!(
+                free_space,
+                "there is enough space already, nothing to delete"
+            );
+            return 0;
+        }
+
+        let mut dir_vector: Vec<(&String, &usize)> = directories.iter().collect();
+        dir_vector.sort_by_key(|d| d.1);
+        // even deleting every directory may not be enough, the root is the most to delete
+        dir_vector
+            .iter()
+            .find(|(_key, value)| *value > &space_to_delete)
+            .map_or(used_space, |(_key, value)| **value)
+    }
+}
+
+/// returns the total size of every directory, keyed by its path like `/a/b` with `` for the root,
+/// directories which are listed but never entered are empty
+fn directory_sizes(input: &str) -> Result<HashMap<String, usize>, ParseError> {
+    let mut directories: HashMap<String, usize> = HashMap::new();
+    // the names of the directories from the root to the current one, none before `$ cd /`
+    let mut path: Option<Vec<&str>> = None;
+    // directories listed already, listing them again must not count their files twice
+    let mut listed = HashSet::new();
+    // whether the lines are the output of `$ ls`, and whether its directory is listed the first time
+    let mut listing = false;
+    let mut first_listing = false;
+
+    for line in error::lines(Day7::DAY, input) {
+        trace!(line = line.text, "parsing");
+
+        if let Some(directory) = line.text.strip_prefix("$ cd ") {
+            listing = false;
+            path = match (path, directory) {
+                (_, "/") => Some(vec![]),
+                (None, _) => None,
+                (Some(mut path), "..") => {
+                    if path.pop().is_none() {
+                        return Err(line.error(directory, "a subdirectory, the root has no parent"));
                     }
+                    Some(path)
                 }
-            } else {
-                location_helper.pop();
-            }
-            location_helper.clone()
-        })
-        .collect();
-
-    location_history.reverse();
-    let mut location_iterator = location_history.iter();
-
-    let final_location = vec!["/".to_string()];
-
-    input.iter().rev().fold(
-        (0, location_iterator.next()),
-        |(size, location), line| {
-            //println!("LINE TO PARSE: {:?}", line);
-            if file_regex.is_match(line) {
-                //println!("\tLine is file");
-                let file_size_str = &file_regex.captures(line).unwrap()[1];
-                let file_size = file_size_str.parse::<usize>().unwrap();
-
-                (size + file_size, location)
-            } else if dir_regex.is_match(line) {
-                let directory_name = &dir_regex.captures(line).unwrap()[1];
-                let loc = location.unwrap().join("/");
-
-                let key = vec![&loc[..], directory_name].join("/");
-                (size + directories[&key], location)
-            } else if ls_regex.is_match(line) {
-                let key = location.unwrap().join("/");
-                directories.insert(key, size);
-
-                (0, location)
+                (Some(mut path), name) => {
+                    path.push(name_of(&line, name, "a directory name")?);
+                    Some(path)
+                }
+            };
+        }
+        let Some(path) = &path else {
+            return Err(line.error(line.text, "the transcript to start with `$ cd /`"));
+        };
+
+        if line.text.starts_with("$ cd ") {
+            continue;
+        } else if line.text == "$ ls" {
+            listing = true;
+            first_listing = listed.insert(key(path));
+            directories.entry(key(path)).or_insert(0);
+        } else if !listing && !line.text.starts_with('$') {
+            return Err(line.error(line.text, "a command, entries only follow `$ ls`"));
+        } else if let Some(directory) = line.text.strip_prefix("dir ") {
+            let name = name_of(&line, directory, "a directory name")?;
+            directories
+                .entry(key(&[&path[..], &[name]].concat()))
+                .or_insert(0);
+        } else if let Some((size, name)) = line
+            .text
+            .split_once(' ')
+            .filter(|_| !line.text.starts_with('$'))
+        {
+            name_of(&line, name, "a file name")?;
+            let size = line.parse::<usize>(size, "a file size")?;
+            if !first_listing {
+                continue;
             }
-            else if cd_regex.is_match(line) || cd_back_regex.is_match(line) {
-                let loc = location_iterator.next().unwrap_or(&final_location);
-
-                (0, Some(loc))
-            } else {
-                panic!("Should not occur")
+            // the file counts towards its directory and every directory above
+            for depth in 0..=path.len() {
+                let total = directories.entry(key(&path[..depth])).or_insert(0);
+                *total = total.checked_add(size).ok_or_else(|| {
+                    line.error(line.text, "a file size the directory totals can hold")
+                })?;
             }
-        },
-    );
-
-    // for (key, value) in &directories {
-        //println!("{:?}: {:?}", key, value);
-    // }
-
-
-    let small_dirs = directories
-        .iter()
-        .filter(|d| *d.1 < 100_000)
-        .map(|d| d.0.to_string())
-        .collect::<Vec<String>>();
-    let result: usize = small_dirs.iter().map(|d| directories[d]).sum();
-
-    let required_space = 30_000_000;
-    let total_space =  70_000_000;
-    let used_space = directories[""];
-    let space_to_delete = required_space - (total_space - used_space);
-
-    let mut dir_vector: Vec<(String, usize)>= directories.into_iter().collect();
-    dir_vector.sort_by(|a, b| a.1.cmp(&b.1));
-    let delete_size = dir_vector.iter().find(|(_key, value)| {
-        value > &space_to_delete
-    }).unwrap().1;
+        } else {
+            return Err(line.error(
+                line.text,
+                "a `cd` or `ls` command, a directory or a file listing",
+            ));
+        }
+    }
+
+    if path.is_none() {
+        return Err(ParseError::new(
+            Day7::DAY,
+            input,
+            "the transcript to start with `$ cd /`",
+        ));
+    }
+    debug!(?directories, "directory sizes");
+
+    Ok(directories)
+}
 
+/// returns the key of the directory at the path
+fn key(path: &[&str]) -> String {
+    path.iter().map(|name| format!("/{}", name)).collect()
+}
 
-    println!("Part 1 {:?}", result);
-    println!("Part 2 {:?}", delete_size);
+/// returns the name of a file or directory, which can't be empty or contain `/`
+fn name_of<'a>(line: &Line, name: &'a str, expected: &str) -> Result<&'a str, ParseError> {
+    match name.is_empty() || name.contains(['/', ' ']) {
+        true => Err(line.error(name, expected)),
+        false => Ok(name),
+    }
 }
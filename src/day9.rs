@@ -1,144 +1,177 @@
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{self, BufRead},
+use std::collections::{HashSet, VecDeque};
+
+use tracing::{debug, trace};
+
+use crate::{
+    error,
+    geom::{Direction, Direction8, Point2},
+    params::{Parameter, Params},
+    viz::{Render, Simulation},
+    ParseError, Solution,
 };
 
-#[derive(Debug)]
-struct Move {
-    direction: String,
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = Vec<Move>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    const PARAMETERS: &'static [Parameter] = &[
+        Parameter {
+            name: "short_rope_size",
+            default: 2,
+            min: 1,
+            max: 1000,
+            description: "knots of the rope of part 1, including the head",
+        },
+        Parameter {
+            name: "long_rope_size",
+            default: 10,
+            min: 1,
+            max: 1000,
+            description: "knots of the rope of part 2, including the head",
+        },
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        error::lines(Self::DAY, input)
+            .map(|line| {
+                let (direction, amount) = line.split_once(" ", "a move like `R 4`")?;
+
+                Ok(Move {
+                    direction: line.parse(direction, "a direction U, D, L or R")?,
+                    amount: line.parse(amount, "an amount of steps")?,
+                })
+            })
+            .collect()
+    }
+
+    fn part1(moves: &Self::Input, params: &Params) -> Self::Answer1 {
+        count_tail_positions(moves, params.get("short_rope_size"))
+    }
+
+    fn part2(moves: &Self::Input, params: &Params) -> Self::Answer2 {
+        count_tail_positions(moves, params.get("long_rope_size"))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Move {
+    direction: Direction,
     amount: i32,
 }
 
-#[derive(PartialEq, Eq, Hash, Debug, Clone)]
-struct Position(i32, i32);
-
-const ROPE_SIZE: usize = 10;
-
-fn main() {
-    let file = File::open("./data/day9.input").expect("file not found!");
-    let moves = io::BufReader::new(file)
-        .lines()
-        .flatten()
-        .collect::<Vec<String>>()
-        .iter()
-        .map(|s| {
-            let (direction, amount) = s.split_once(' ').unwrap();
-            Move {
-                direction: direction.to_string(),
-                amount: amount.parse().unwrap(),
-            }
-        })
-        .collect::<Vec<Move>>();
-
-    let mut visited_positions: HashSet<Position> = HashSet::new();
-
-    // all knots are on the same position
-    let rope: Vec<Position> = vec![Position(0, 0); ROPE_SIZE];
-
-    // for every move, move the head and let the tails follow, returning a new rope state
-    moves.iter().fold(
-        rope,
-        |mut rope_after_complete_move, move_action| -> Vec<Position> {
-            // take the head of the rope
-            let mut new_head = rope_after_complete_move[ROPE_SIZE - 1].clone();
-
-            // repeat the move head and tail sequence X amount of times according to the input
-            for _ in 0..move_action.amount {
-
-                // println!("Old head {:?}", new_head);
-                // move head
-                new_head = match &move_action.direction[..] {
-                    "U" => Position(new_head.0, new_head.1 + 1),
-                    "D" => Position(new_head.0, new_head.1 - 1),
-                    "R" => Position(new_head.0 + 1, new_head.1),
-                    "L" => Position(new_head.0 - 1, new_head.1),
-                    _ => panic!("Not a valid move"),
-                };
-                rope_after_complete_move[ROPE_SIZE - 1] = new_head.clone();
-                // println!("New head {:?}", new_head);
-
-                for i in (0..(ROPE_SIZE - 1)).rev() {
-                    // take the head which is already moved
-                    let moved_head = rope_after_complete_move[i + 1].clone();
-                    // take the tail which needs to be updated at index i
-                    let mut following_tail = rope_after_complete_move[i].clone();
-
-                    // println!("Old tail {:?}", following_tail);
-                    // where is the head in comparison to the tail
-                    let movement_direction = (
-                        moved_head.0 - following_tail.0,
-                        moved_head.1 - following_tail.1,
-                    );
-
-                    // println!("ABS movement {}, {}", movement_direction.0.abs(), movement_direction.1.abs());
-                    // println!("Movement {}, {}", movement_direction.0, movement_direction.1);
-                    // update the following tail
-                    following_tail = match movement_direction {
-                        (a, b) if a.abs() < 2 && b.abs() < 2 => {
-                            Position(following_tail.0, following_tail.1)
-                        }
-                        // up
-                        (a, b) if a == 0 && b > 0 => {
-                            // println!("Going up");
-                            Position(following_tail.0, following_tail.1 + 1)
-                        }
-                        // down
-                        (a, b) if a == 0 && b < 0 => {
-                            // println!("Going down");
-                            Position(following_tail.0, following_tail.1 - 1)
-                        }
-                        // left
-                        (a, b) if a < 0 && b == 0 => {
-                            // println!("Going left");
-                            Position(following_tail.0 - 1, following_tail.1)
-                        }
-                        // right
-                        (a, b) if a > 0 && b == 0 => {
-                            // println!("Going right");
-                            Position(following_tail.0 + 1, following_tail.1)
-                        }
-                        // right up
-                        (a, b) if a > 0 && b > 0 => {
-                            // println!("Going right up");
-                            Position(following_tail.0 + 1, following_tail.1 + 1)
-                        }
-                        // right down
-                        (a, b) if a > 0 && b < 0 => {
-                            // println!("Going right down");
-                            Position(following_tail.0 + 1, following_tail.1 - 1)
-                        }
-                        // left up
-                        (a, b) if a < 0 && b > 0 => {
-                            // println!("Going left up");
-                            Position(following_tail.0 - 1, following_tail.1 + 1)
-                        }
-                        // left down
-                        (a, b) if a < 0 && b < 0 => {
-                            // println!("Going left down");
-                            Position(following_tail.0 - 1, following_tail.1 - 1)
-                        }
-                        _ => {
-                            panic!("SHIIT");
-                        }
-                    };
-
-                    // println!("New tail {:?}", following_tail);
-                    // println!("------");
-
-                    // update the rope
-                    rope_after_complete_move[i] = following_tail.clone();
-
-                    if i == 0 {
-                        // save tails position
-                        visited_positions.insert(following_tail.clone());
-                    };
+/// returns the amount of positions the tail of a rope with `rope_size` knots visits
+fn count_tail_positions(moves: &[Move], rope_size: usize) -> usize {
+    let mut rope = Rope::new(rope_size, moves);
+    while rope.step() {}
+
+    rope.visited.len()
+}
+
+/// A rope whose head makes its moves one step at a time.
+pub struct Rope {
+    /// the tail comes first, the head last
+    knots: Vec<Point2>,
+    /// positions the tail visited
+    visited: HashSet<Point2>,
+    /// the moves left to make, the first one is in progress
+    moves: VecDeque<Move>,
+    /// steps made of the move in progress
+    progress: i32,
+}
+
+impl Rope {
+    /// all knots start on the same position
+    pub fn new(rope_size: usize, moves: &[Move]) -> Self {
+        Rope {
+            knots: vec![Point2::ORIGIN; rope_size],
+            visited: HashSet::new(),
+            moves: moves.iter().copied().collect(),
+            progress: 0,
+        }
+    }
+}
+
+impl Simulation for Rope {
+    /// moves the head a single step and lets the tails follow
+    fn step(&mut self) -> bool {
+        // repeat the move head and tail sequence X amount of times according to the input
+        let direction = loop {
+            let Some(move_action) = self.moves.front() else {
+                return false;
+            };
+            if self.progress < move_action.amount {
+                if self.progress == 0 {
+                    debug!(direction = ?move_action.direction, amount = move_action.amount, "move");
                 }
+                self.progress += 1;
+                break move_action.direction;
             }
+            self.moves.pop_front();
+            self.progress = 0;
+        };
+
+        // move head
+        let head = self.knots.len() - 1;
+        self.knots[head] += direction.offset();
+        trace!(head = ?self.knots[head], "moved head");
+
+        for i in (0..head).rev() {
+            // take the head which is already moved
+            let moved_head = self.knots[i + 1];
+            // take the tail which needs to be updated at index i
+            let following_tail = &mut self.knots[i];
+
+            // the tail only follows when the head is no longer touching it
+            if moved_head.chebyshev_distance(*following_tail) > 1 {
+                // where is the head in comparison to the tail, one step at most
+                let step = (moved_head - *following_tail).signum();
+                *following_tail += step;
+                trace!(
+                    knot = i,
+                    going = ?Direction8::from_offset(step),
+                    tail = ?following_tail,
+                    "moved tail"
+                );
+            }
+        }
+        // save tails position
+        self.visited.insert(self.knots[0]);
 
-            rope_after_complete_move.clone()
-        },
-    );
+        true
+    }
+}
+
+/// the size of the part of the grid drawn around the head
+const VIEW: (isize, isize) = (60, 24);
+
+impl Render for Rope {
+    /// draws the knots around the head like the puzzle, `H` for the head, numbers for the
+    /// knots behind it and `#` for the positions the tail visited
+    fn render(&self) -> String {
+        let head = self.knots[self.knots.len() - 1];
+        let (left, top) = (head.x - VIEW.0 / 2, head.y - VIEW.1 / 2);
+
+        let mut drawing = String::new();
+        for y in top..top + VIEW.1 {
+            for x in left..left + VIEW.0 {
+                let point = Point2::new(x, y);
+                let knot = self.knots.iter().rev().position(|knot| *knot == point);
+                drawing.push(match knot {
+                    Some(0) => 'H',
+                    Some(knot) => char::from_digit((knot % 10) as u32, 10).unwrap(),
+                    None if point == Point2::ORIGIN => 's',
+                    None if self.visited.contains(&point) => '#',
+                    None => '.',
+                });
+            }
+            drawing.push('\n');
+        }
 
-    println!("Visited positions: {:?}", visited_positions.len());
+        drawing
+    }
 }
@@ -0,0 +1,138 @@
+//! Parse errors pointing at the offending part of the puzzle input.
+
+use std::{error::Error, fmt, str::FromStr};
+
+/// A puzzle input which does not have the expected shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line of the input
+    pub line: usize,
+    /// 1-based column of the offending text within the line
+    pub column: usize,
+    /// the offending text
+    pub text: String,
+    /// description of what was expected instead
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(day: u8, text: &str, expected: impl Into<String>) -> Self {
+        ParseError {
+            day,
+            line: 1,
+            column: 1,
+            text: text.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// moves the error `lines` lines down, for parsers which only see part of the input
+    pub fn below(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found {:?}",
+            self.day, self.line, self.column, self.expected, self.text
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// A line of the puzzle input, remembering where it is for error reporting.
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    pub day: u8,
+    /// 1-based line number
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// a line which is parsed on its own, errors are moved to the right line with [`ParseError::below`]
+    pub fn single(day: u8, text: &'a str) -> Self {
+        Line {
+            day,
+            number: 1,
+            text,
+        }
+    }
+
+    /// creates an error for `offending`, which should be a slice of the line to know its column
+    pub fn error(&self, offending: &str, expected: impl Into<String>) -> ParseError {
+        let offset = (offending.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        let column = if offset <= self.text.len() {
+            offset + 1
+        } else {
+            1
+        };
+
+        ParseError {
+            column,
+            line: self.number,
+            ..ParseError::new(self.day, offending, expected)
+        }
+    }
+
+    /// parses `text`, a slice of the line, into a value
+    pub fn parse<T: FromStr>(&self, text: &str, expected: &str) -> Result<T, ParseError> {
+        text.parse().map_err(|_| self.error(text, expected))
+    }
+
+    /// splits the line once around `delimiter`
+    pub fn split_once(
+        &self,
+        delimiter: &str,
+        expected: &str,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        self.text
+            .split_once(delimiter)
+            .ok_or_else(|| self.error(self.text, expected))
+    }
+}
+
+/// returns every line of the input, numbered for error reporting
+pub fn lines(day: u8, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(move |(index, text)| Line {
+        day,
+        number: index + 1,
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_at_offending_text() {
+        let line = lines(5, "move 1 from 2 to 3\nmove x from 1 to 2")
+            .nth(1)
+            .unwrap();
+
+        let error = line
+            .parse::<usize>(&line.text[5..6], "an amount")
+            .unwrap_err();
+
+        assert_eq!((error.line, error.column), (2, 6));
+        assert_eq!(
+            error.to_string(),
+            "day 5, line 2, column 6: expected an amount, found \"x\""
+        );
+    }
+
+    #[test]
+    fn moves_error_below() {
+        let line = Line::single(11, "Monkey x:");
+        let error = line.error(&line.text[7..8], "a number").below(7);
+
+        assert_eq!((error.line, error.column), (8, 8));
+    }
+}
@@ -0,0 +1,179 @@
+//! Downloading puzzle inputs, once, and submitting answers.
+//!
+//! The session token is read from `AOC_SESSION`, or else from `session` in the config file
+//! `$XDG_CONFIG_HOME/aoc/config.toml` (`~/.config/aoc/config.toml`).
+//! The server is `https://adventofcode.com` unless `AOC_BASE_URL` or `base_url` says otherwise.
+
+use std::{
+    env,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::Deserialize;
+
+use crate::ledger::Status;
+
+pub const YEAR: u16 = 2022;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// value of the `session` cookie of a logged in user
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    NoSession,
+    Config(PathBuf, String),
+    Http(String),
+    /// the server did not judge a submitted answer
+    Submission(String),
+    Io(io::Error),
+}
+
+/// How an input ended up on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    Cached,
+    Downloaded,
+}
+
+impl Config {
+    /// reads the config file if it exists, the environment overrides it
+    pub fn load() -> Result<Self, FetchError> {
+        let mut config = match config_path() {
+            Some(path) if path.exists() => {
+                let config = fs::read_to_string(&path).map_err(FetchError::Io)?;
+                toml::from_str(&config)
+                    .map_err(|error| FetchError::Config(path, error.to_string()))?
+            }
+            _ => Config::default(),
+        };
+
+        if let Ok(session) = env::var("AOC_SESSION") {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var("AOC_BASE_URL") {
+            config.base_url = Some(base_url);
+        }
+
+        Ok(config)
+    }
+
+    pub fn base_url(&self) -> &str {
+        self.base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BASE_URL)
+            .trim_end_matches('/')
+    }
+
+    /// builds a request to the server, logged in with the session token
+    pub fn request(&self, method: &str, path: &str) -> Result<ureq::Request, FetchError> {
+        // an empty token in the environment is no token
+        let session = self
+            .session
+            .as_deref()
+            .map(str::trim)
+            .filter(|session| !session.is_empty())
+            .ok_or(FetchError::NoSession)?;
+
+        Ok(ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .request(method, &format!("{}{}", self.base_url(), path))
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", "github.com/aaronhallaert/aoc2022"))
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+
+    Some(config_home.join("aoc/config.toml"))
+}
+
+/// downloads the input of the day into `path`, unless it is already there
+pub fn fetch_input(config: &Config, day: u8, path: &Path) -> Result<Fetched, FetchError> {
+    if path.exists() {
+        return Ok(Fetched::Cached);
+    }
+
+    let response = config
+        .request("GET", &format!("/{}/day/{}/input", YEAR, day))?
+        .call()
+        .map_err(|error| FetchError::Http(error.to_string()))?;
+    let input = response.into_string().map_err(FetchError::Io)?;
+
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).map_err(FetchError::Io)?;
+    }
+    fs::write(path, input).map_err(FetchError::Io)?;
+
+    Ok(Fetched::Downloaded)
+}
+
+/// submits the answer of `part` of `day`, returns what the server said about it
+pub fn submit_answer(
+    config: &Config,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Status, FetchError> {
+    let response = config
+        .request("POST", &format!("/{}/day/{}/answer", YEAR, day))?
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        .map_err(|error| FetchError::Http(error.to_string()))?;
+    let page = response.into_string().map_err(FetchError::Io)?;
+
+    submission_status(&page)
+}
+
+/// reads the verdict from the page the server replies to a submission with
+pub fn submission_status(page: &str) -> Result<Status, FetchError> {
+    if page.contains("That's the right answer") {
+        Ok(Status::Accepted)
+    } else if page.contains("your answer is too high") {
+        Ok(Status::TooHigh)
+    } else if page.contains("your answer is too low") {
+        Ok(Status::TooLow)
+    } else if page.contains("That's not the right answer") {
+        Ok(Status::Wrong)
+    } else if page.contains("You gave an answer too recently") {
+        Err(FetchError::Submission(
+            "an answer was submitted too recently, wait a while".to_string(),
+        ))
+    } else if page.contains("You don't seem to be solving the right level") {
+        Err(FetchError::Submission(
+            "this part is already solved or still locked".to_string(),
+        ))
+    } else {
+        Err(FetchError::Submission(
+            "the reply of the server is not understood".to_string(),
+        ))
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::NoSession => write!(
+                f,
+                "no session token, set AOC_SESSION or `session` in ~/.config/aoc/config.toml"
+            ),
+            FetchError::Config(path, error) => write!(f, "invalid {}: {}", path.display(), error),
+            FetchError::Http(error) => write!(f, "request failed: {}", error),
+            FetchError::Submission(error) => write!(f, "submission failed: {}", error),
+            FetchError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for FetchError {}
@@ -0,0 +1,576 @@
+//! Random puzzle inputs, valid for the parser and solvable by the solver of their day.
+//!
+//! Every generator takes a `size`, the amount of lines, rounds or cells it scales with,
+//! and draws from an [`Rng`] seeded by the caller, so a seed always gives the same input.
+
+use std::collections::BTreeSet;
+
+/// A small deterministic random number generator, SplitMix64.
+///
+/// Its sequence never changes, unlike those of external crates, so inputs stay reproducible.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// returns a number below `bound`, which should be positive
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// returns a number between `low` and `high`, both included
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.next_u64() % (high - low + 1) as u64) as i64
+    }
+
+    /// returns true once every `n` times on average
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// returns an input for the day, `None` if the day has no generator
+pub fn generate(day: u8, rng: &mut Rng, size: usize) -> Option<String> {
+    let generator = match day {
+        1 => day1,
+        2 => day2,
+        3 => day3,
+        4 => day4,
+        5 => day5,
+        6 => day6,
+        7 => day7,
+        8 => day8,
+        9 => day9,
+        10 => day10,
+        11 => day11,
+        12 => day12,
+        13 => day13,
+        14 => day14,
+        15 => day15,
+        16 => day16,
+        17 => day17,
+        18 => day18,
+        _ => return None,
+    };
+
+    Some(generator(rng, size.max(1)))
+}
+
+/// `size` elves carrying a few calories each, at least three
+pub fn day1(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(3))
+        .map(|_| {
+            (0..rng.between(1, 10))
+                .map(|_| format!("{}\n", rng.between(1000, 60000)))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// `size` rounds of rock paper scissors
+pub fn day2(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.pick(&['A', 'B', 'C']),
+                rng.pick(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect()
+}
+
+/// `size` groups of three rucksacks, every rucksack has one item in both compartments
+/// and every group has one badge
+pub fn day3(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut letters = LETTERS.to_vec();
+        rng.shuffle(&mut letters);
+        // the badge, the shared item of each rucksack and two disjoint pools per rucksack
+        let (badge, letters) = letters.split_first().unwrap();
+        let (shared, pools) = letters.split_at(3);
+
+        for (rucksack, pools) in pools.chunks(16).enumerate() {
+            let (left_pool, right_pool) = pools.split_at(8);
+            let length = rng.between(1, 12) as usize;
+
+            let mut left = vec![shared[rucksack], *badge];
+            left.extend((0..length).map(|_| *rng.pick(left_pool)));
+            let mut right = vec![shared[rucksack]];
+            right.extend((0..=length).map(|_| *rng.pick(right_pool)));
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+            if rng.one_in(2) {
+                std::mem::swap(&mut left, &mut right);
+            }
+
+            input.push_str(&String::from_utf8(left).unwrap());
+            input.push_str(&String::from_utf8(right).unwrap());
+            input.push('\n');
+        }
+    }
+
+    input
+}
+
+/// `size` pairs of section assignments
+pub fn day4(rng: &mut Rng, size: usize) -> String {
+    let assignment = |rng: &mut Rng| {
+        let start = rng.between(1, 99);
+        (start, rng.between(start, 99))
+    };
+
+    (0..size)
+        .map(|_| {
+            let (a, b) = assignment(rng);
+            let (c, d) = assignment(rng);
+            format!("{}-{},{}-{}\n", a, b, c, d)
+        })
+        .collect()
+}
+
+/// up to nine stacks, followed by `size` moves which never take more containers than a stack has
+pub fn day5(rng: &mut Rng, size: usize) -> String {
+    let stack_count = rng.between(2, 9) as usize;
+    let mut stacks = (0..stack_count)
+        .map(|_| {
+            (0..rng.between(1, 8))
+                .map(|_| *rng.pick(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ") as char)
+                .collect::<Vec<char>>()
+        })
+        .collect::<Vec<Vec<char>>>();
+
+    let mut input = String::new();
+    let highest = stacks.iter().map(Vec::len).max().unwrap_or_default();
+    for level in (0..highest).rev() {
+        let row = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(container) => format!("[{}]", container),
+                None => "   ".to_string(),
+            })
+            .collect::<Vec<String>>();
+        input.push_str(&row.join(" "));
+        input.push('\n');
+    }
+    let numbers = (1..=stack_count)
+        .map(|number| format!(" {} ", number))
+        .collect::<Vec<String>>();
+    input.push_str(&numbers.join(" "));
+    input.push_str("\n\n");
+
+    for _ in 0..size {
+        let non_empty = (0..stack_count)
+            .filter(|stack| !stacks[*stack].is_empty())
+            .collect::<Vec<usize>>();
+        let from = *rng.pick(&non_empty);
+        let to = (from + rng.between(1, stack_count as i64 - 1) as usize) % stack_count;
+        let amount = rng.between(1, stacks[from].len() as i64) as usize;
+
+        let at = stacks[from].len() - amount;
+        let containers = stacks[from].split_off(at);
+        stacks[to].extend(containers);
+        input.push_str(&format!(
+            "move {} from {} to {}\n",
+            amount,
+            from + 1,
+            to + 1
+        ));
+    }
+
+    input
+}
+
+/// a datastream of `size` letters, ending with the first run of fourteen distinct letters
+pub fn day6(rng: &mut Rng, size: usize) -> String {
+    // a small alphabet repeats letters too often for a marker
+    let mut datastream = (0..size)
+        .map(|_| *rng.pick(&LOWERCASE[..3]) as char)
+        .collect::<String>();
+
+    let mut marker = LOWERCASE.to_vec();
+    rng.shuffle(&mut marker);
+    datastream.extend(marker[..14].iter().map(|letter| *letter as char));
+    datastream.push('\n');
+
+    datastream
+}
+
+/// a terminal transcript exploring `size` directories, which use more than 40000000 together
+pub fn day7(rng: &mut Rng, size: usize) -> String {
+    // the subdirectories of every directory, directory 0 is the root
+    let mut children = vec![vec![]; size];
+    for directory in 1..size {
+        children[rng.below(directory)].push(directory);
+    }
+
+    fn explore(rng: &mut Rng, directory: usize, children: &[Vec<usize>], transcript: &mut String) {
+        transcript.push_str("$ ls\n");
+        for child in &children[directory] {
+            transcript.push_str(&format!("dir d{}\n", child));
+        }
+        for file in 0..rng.below(4) {
+            transcript.push_str(&format!("{} f{}.txt\n", rng.between(1, 300_000), file));
+        }
+        if directory == 0 {
+            // there should be a directory worth deleting for the update
+            transcript.push_str(&format!("{} system\n", rng.between(40_000_000, 50_000_000)));
+        }
+
+        for child in &children[directory] {
+            transcript.push_str(&format!("$ cd d{}\n", child));
+            explore(rng, *child, children, transcript);
+            transcript.push_str("$ cd ..\n");
+        }
+    }
+
+    let mut transcript = "$ cd /\n".to_string();
+    explore(rng, 0, &children, &mut transcript);
+
+    transcript
+}
+
+/// a square grid of trees, `size` wide
+pub fn day8(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut row = (0..size)
+                .map(|_| char::from_digit(rng.below(10) as u32, 10).unwrap())
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+/// `size` moves of the head of the rope
+pub fn day9(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.pick(&['U', 'D', 'L', 'R']),
+                rng.between(1, 20)
+            )
+        })
+        .collect()
+}
+
+/// `size` instructions, at least enough to draw the whole screen,
+/// which keep the sprite on the screen
+pub fn day10(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    let mut register = 1;
+    let mut cycles = 0;
+    let mut instructions = 0;
+    while instructions < size || cycles < 240 {
+        if rng.one_in(3) {
+            input.push_str("noop\n");
+            cycles += 1;
+        } else {
+            let target = rng.between(1, 38);
+            input.push_str(&format!("addx {}\n", target - register));
+            register = target;
+            cycles += 2;
+        }
+        instructions += 1;
+    }
+
+    input
+}
+
+/// `size` items spread over as many monkeys, at least two and at most nine,
+/// which throw them to each other
+pub fn day11(rng: &mut Rng, size: usize) -> String {
+    let monkeys = size.clamp(2, 9);
+    let mut divisors = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    rng.shuffle(&mut divisors);
+    let mut items = vec![vec![]; monkeys];
+    for _ in 0..size {
+        items[rng.below(monkeys)].push(rng.between(50, 99).to_string());
+    }
+
+    (0..monkeys)
+        .map(|monkey| {
+            // with relief the worry levels should not grow, or they overflow
+            let operation = match rng.below(3) {
+                0 => "old + old".to_string(),
+                1 => format!("old * {}", rng.between(2, 3)),
+                _ => format!("old + {}", rng.between(1, 9)),
+            };
+            let target = |rng: &mut Rng| (monkey + rng.between(1, monkeys as i64 - 1) as usize) % monkeys;
+            let if_true = target(rng);
+            let mut if_false = target(rng);
+            if monkeys > 2 {
+                while if_false == if_true {
+                    if_false = target(rng);
+                }
+            }
+
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                monkey,
+                items[monkey].join(", "),
+                operation,
+                divisors[monkey],
+                if_true,
+                if_false
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// a map `size` wide and high, at least 14, climbing from S in the top left corner
+/// to E in the bottom right one, with cliffs everywhere but the top row and right column
+pub fn day12(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(14);
+    // the elevation rises by at most one with every step towards E
+    let steps = 2 * side - 2;
+
+    (0..side)
+        .map(|y| {
+            let mut row = (0..side)
+                .map(|x| match (x, y) {
+                    (0, 0) => 'S',
+                    _ if x == side - 1 && y == side - 1 => 'E',
+                    _ if y != 0 && x != side - 1 && rng.one_in(4) => 'z',
+                    _ => LOWERCASE[(x + y) * 25 / steps] as char,
+                })
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+/// `size` pairs of packets
+pub fn day13(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0)))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// returns a list nested at most four levels deep
+fn packet(rng: &mut Rng, depth: usize) -> String {
+    let values = (0..rng.below(5))
+        .map(|_| match depth < 3 && rng.one_in(3) {
+            true => packet(rng, depth + 1),
+            false => rng.below(11).to_string(),
+        })
+        .collect::<Vec<String>>();
+
+    format!("[{}]", values.join(","))
+}
+
+/// `size` paths of rock below the source of the sand at 500,0
+pub fn day14(rng: &mut Rng, size: usize) -> String {
+    let spread = 5 + size as i64;
+    // the paths should not reach beyond x=0
+    let width = spread.min(400);
+
+    (0..size)
+        .map(|_| {
+            let mut x = rng.between(500 - width, 500 + width);
+            let mut y = rng.between(1, spread);
+            let mut points = vec![format!("{},{}", x, y)];
+            for segment in 0..rng.between(1, 4) {
+                let length = rng.between(1, 6) * if rng.one_in(2) { 1 } else { -1 };
+                // the path turns at every point and stays below the source
+                if segment % 2 == 0 {
+                    x += length;
+                } else {
+                    y = (y + length).max(1);
+                    if points.last() == Some(&format!("{},{}", x, y)) {
+                        y += 1;
+                    }
+                }
+                points.push(format!("{},{}", x, y));
+            }
+            format!("{}\n", points.join(" -> "))
+        })
+        .collect()
+}
+
+/// `size` sensors between 0 and 4000000, none of them covers the same position,
+/// so there is room for the distress beacon
+pub fn day15(rng: &mut Rng, size: usize) -> String {
+    const MAX_RANGE: i64 = 4_000_000;
+    // the sensors cover a smaller area the more there are
+    let reach = (MAX_RANGE / (size as i64 * 16)).max(2);
+    let distress = (rng.between(0, MAX_RANGE), rng.between(0, MAX_RANGE));
+
+    let mut input = String::new();
+    let mut sensors = 0;
+    while sensors < size {
+        let sensor = (rng.between(0, MAX_RANGE), rng.between(0, MAX_RANGE));
+        let to_distress = (sensor.0 - distress.0).abs() + (sensor.1 - distress.1).abs();
+        if to_distress < 2 {
+            continue;
+        }
+
+        let distance = rng.between(1, reach.min(to_distress - 1));
+        let dx = rng.between(-distance, distance);
+        let dy = (distance - dx.abs()) * if rng.one_in(2) { 1 } else { -1 };
+        input.push_str(&format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+            sensor.0,
+            sensor.1,
+            sensor.0 + dx,
+            sensor.1 + dy
+        ));
+        sensors += 1;
+    }
+
+    input
+}
+
+/// `size` valves besides AA, all reachable from AA, at most fifteen of them have a flow
+pub fn day16(rng: &mut Rng, size: usize) -> String {
+    let valves = (size + 1).min(26 * 26);
+    let mut names = (0..26 * 26)
+        .map(|index| {
+            let name = [b'A' + (index / 26) as u8, b'A' + (index % 26) as u8];
+            String::from_utf8(name.to_vec()).unwrap()
+        })
+        .filter(|name| name != "AA")
+        .collect::<Vec<String>>();
+    rng.shuffle(&mut names);
+    names.insert(0, "AA".to_string());
+    names.truncate(valves);
+
+    // a random tree connects every valve, a few more tunnels make loops
+    let mut tunnels = vec![BTreeSet::new(); valves];
+    for valve in 1..valves {
+        let other = rng.below(valve);
+        tunnels[valve].insert(other);
+        tunnels[other].insert(valve);
+    }
+    for _ in 0..valves / 3 {
+        let (a, b) = (rng.below(valves), rng.below(valves));
+        if a != b {
+            tunnels[a].insert(b);
+            tunnels[b].insert(a);
+        }
+    }
+
+    let mut flowing = (1..valves).collect::<Vec<usize>>();
+    rng.shuffle(&mut flowing);
+    flowing.truncate(15);
+
+    (0..valves)
+        .map(|valve| {
+            let rate = match flowing.contains(&valve) {
+                true => rng.between(1, 25),
+                false => 0,
+            };
+            let others = tunnels[valve]
+                .iter()
+                .map(|other| names[*other].as_str())
+                .collect::<Vec<&str>>();
+            let tunnels = match others[..] {
+                [other] => format!("tunnel leads to valve {}", other),
+                _ => format!("tunnels lead to valves {}", others.join(", ")),
+            };
+            format!(
+                "Valve {} has flow rate={}; {}\n",
+                names[valve], rate, tunnels
+            )
+        })
+        .collect()
+}
+
+/// a pattern of `size` jets, at least 1000
+pub fn day17(rng: &mut Rng, size: usize) -> String {
+    // the tower only repeats itself once blocks landed in every column,
+    // a short pattern might always push them away from a wall
+    let mut jets = (0..size.max(1000))
+        .map(|_| *rng.pick(&['<', '>']))
+        .collect::<String>();
+    jets.push('\n');
+
+    jets
+}
+
+/// `size` distinct cubes, packed closely enough to touch and enclose air
+pub fn day18(rng: &mut Rng, size: usize) -> String {
+    let side = (size as f64).cbrt().ceil() as i64 + 2;
+    let mut cubes = BTreeSet::new();
+    while cubes.len() < size.min((side * side * side) as usize) {
+        cubes.insert((
+            rng.between(0, side),
+            rng.between(0, side),
+            rng.between(0, side),
+        ));
+    }
+
+    cubes
+        .iter()
+        .map(|(x, y, z)| format!("{},{},{}\n", x, y, z))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_input() {
+        for day in 1..=18 {
+            let input = generate(day, &mut Rng::new(7), 10).unwrap();
+            assert_eq!(generate(day, &mut Rng::new(7), 10).unwrap(), input);
+            assert_ne!(generate(day, &mut Rng::new(8), 10).unwrap(), input);
+        }
+        assert_eq!(generate(19, &mut Rng::new(7), 10), None);
+    }
+
+    #[test]
+    fn day11_items_grow_with_the_size() {
+        let items = |size| {
+            day11(&mut Rng::new(1), size)
+                .lines()
+                .filter_map(|line| line.strip_prefix("  Starting items: "))
+                .flat_map(|items| items.split(", ").filter(|item| !item.is_empty()))
+                .count()
+        };
+
+        assert_eq!(items(1), 1);
+        assert_eq!(items(500), 500);
+    }
+
+    #[test]
+    fn rng_stays_within_bounds() {
+        let mut rng = Rng::new(0);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.between(-3, 3)));
+            assert!(rng.below(5) < 5);
+        }
+    }
+}
@@ -0,0 +1,363 @@
+//! Points in 2 and 3 dimensions and the directions to move between them.
+
+use std::{
+    error::Error,
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::grid::Position;
+
+/// A point on a plane, y grows downwards like the rows of a [`Grid`](crate::Grid).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// A point in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Point2 {
+    pub const ORIGIN: Point2 = Point2 { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Point2 { x, y }
+    }
+
+    /// returns the amount of orthogonal steps between the points
+    pub fn manhattan_distance(self, other: Point2) -> usize {
+        (self.x - other.x).unsigned_abs() + (self.y - other.y).unsigned_abs()
+    }
+
+    /// returns the amount of orthogonal or diagonal steps between the points
+    pub fn chebyshev_distance(self, other: Point2) -> usize {
+        (self.x - other.x)
+            .unsigned_abs()
+            .max((self.y - other.y).unsigned_abs())
+    }
+
+    /// returns the point with every coordinate reduced to -1, 0 or 1
+    pub fn signum(self) -> Point2 {
+        Point2::new(self.x.signum(), self.y.signum())
+    }
+
+    /// returns the orthogonal neighbours
+    pub fn neighbours4(self) -> impl Iterator<Item = Point2> {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| self + direction.offset())
+    }
+
+    /// returns the orthogonal and diagonal neighbours
+    pub fn neighbours8(self) -> impl Iterator<Item = Point2> {
+        Direction8::ALL
+            .into_iter()
+            .map(move |direction| self + direction.offset())
+    }
+
+    /// returns the position of the point in a grid, if both coordinates are non-negative
+    pub fn to_position(self) -> Option<Position> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Point3 { x, y, z }
+    }
+
+    /// returns the amount of orthogonal steps between the points
+    pub fn manhattan_distance(self, other: Point3) -> usize {
+        (self.x - other.x).unsigned_abs()
+            + (self.y - other.y).unsigned_abs()
+            + (self.z - other.z).unsigned_abs()
+    }
+
+    /// returns the 6 points sharing a face with the point
+    pub fn neighbours6(self) -> impl Iterator<Item = Point3> {
+        [
+            Point3::new(1, 0, 0),
+            Point3::new(-1, 0, 0),
+            Point3::new(0, 1, 0),
+            Point3::new(0, -1, 0),
+            Point3::new(0, 0, 1),
+            Point3::new(0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |offset| self + offset)
+    }
+}
+
+impl From<Position> for Point2 {
+    fn from((x, y): Position) -> Self {
+        Point2::new(x as isize, y as isize)
+    }
+}
+
+impl From<(isize, isize)> for Point2 {
+    fn from((x, y): (isize, isize)) -> Self {
+        Point2::new(x, y)
+    }
+}
+
+impl From<(isize, isize, isize)> for Point3 {
+    fn from((x, y, z): (isize, isize, isize)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+/// implements the arithmetic of a point coordinate by coordinate
+macro_rules! point_arithmetic {
+    ($point:ident { $($coordinate:ident),+ }) => {
+        impl Add for $point {
+            type Output = $point;
+
+            fn add(self, other: $point) -> $point {
+                $point { $($coordinate: self.$coordinate + other.$coordinate),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = $point;
+
+            fn sub(self, other: $point) -> $point {
+                $point { $($coordinate: self.$coordinate - other.$coordinate),+ }
+            }
+        }
+
+        impl Mul<isize> for $point {
+            type Output = $point;
+
+            fn mul(self, factor: isize) -> $point {
+                $point { $($coordinate: self.$coordinate * factor),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = $point;
+
+            fn neg(self) -> $point {
+                $point { $($coordinate: -self.$coordinate),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, other: $point) {
+                *self = *self + other;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, other: $point) {
+                *self = *self - other;
+            }
+        }
+    };
+}
+
+point_arithmetic!(Point2 { x, y });
+point_arithmetic!(Point3 { x, y, z });
+
+/// An orthogonal direction on a plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+/// An orthogonal or diagonal direction on a plane, named after the points of a compass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+/// A direction which could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownDirection(pub String);
+
+impl fmt::Display for UnknownDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown direction {:?}", self.0)
+    }
+}
+
+impl Error for UnknownDirection {}
+
+impl Direction {
+    /// clockwise, starting up
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// returns the step to take in the direction
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::Down => Point2::new(0, 1),
+            Direction::Left => Point2::new(-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+impl Direction8 {
+    /// clockwise, starting north
+    pub const ALL: [Direction8; 8] = [
+        Direction8::N,
+        Direction8::NE,
+        Direction8::E,
+        Direction8::SE,
+        Direction8::S,
+        Direction8::SW,
+        Direction8::W,
+        Direction8::NW,
+    ];
+
+    /// returns the step to take in the direction
+    pub fn offset(self) -> Point2 {
+        match self {
+            Direction8::N => Point2::new(0, -1),
+            Direction8::NE => Point2::new(1, -1),
+            Direction8::E => Point2::new(1, 0),
+            Direction8::SE => Point2::new(1, 1),
+            Direction8::S => Point2::new(0, 1),
+            Direction8::SW => Point2::new(-1, 1),
+            Direction8::W => Point2::new(-1, 0),
+            Direction8::NW => Point2::new(-1, -1),
+        }
+    }
+
+    /// returns the direction of a single step, `None` for the origin or a longer step
+    pub fn from_offset(offset: Point2) -> Option<Direction8> {
+        Direction8::ALL
+            .into_iter()
+            .find(|direction| direction.offset() == offset)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => Direction8::N,
+            Direction::Right => Direction8::E,
+            Direction::Down => Direction8::S,
+            Direction::Left => Direction8::W,
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = UnknownDirection;
+
+    /// parses `U`, `R`, `D`, `L` or the arrows `^`, `>`, `v`, `<`
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | '^' => Ok(Direction::Up),
+            'R' | '>' => Ok(Direction::Right),
+            'D' | 'v' => Ok(Direction::Down),
+            'L' | '<' => Ok(Direction::Left),
+            _ => Err(UnknownDirection(c.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = UnknownDirection;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(UnknownDirection(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = UnknownDirection;
+
+    /// parses the points of a compass, like `N` or `SW`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Direction8::ALL
+            .into_iter()
+            .find(|direction| format!("{:?}", direction) == s)
+            .ok_or_else(|| UnknownDirection(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_distances() {
+        let a = Point2::new(2, 18);
+        let b = Point2::new(-2, 15);
+
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!((a - b).signum(), Point2::new(1, 1));
+        assert_eq!(Point3::new(1, 2, 3).manhattan_distance(Point3::ORIGIN), 6);
+    }
+
+    #[test]
+    fn parses_directions() {
+        assert_eq!("U".parse(), Ok(Direction::Up));
+        assert_eq!(Direction::try_from('<'), Ok(Direction::Left));
+        assert_eq!("SW".parse(), Ok(Direction8::SW));
+        assert!("X".parse::<Direction>().is_err());
+        assert!("UR".parse::<Direction>().is_err());
+    }
+
+    #[test]
+    fn turns_and_steps() {
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Down.opposite(), Direction::Up);
+        assert_eq!(
+            Direction8::from_offset(Point2::new(-1, 1)),
+            Some(Direction8::SW)
+        );
+        assert_eq!(Point2::ORIGIN.neighbours8().count(), 8);
+        assert_eq!(
+            Point3::ORIGIN
+                .neighbours6()
+                .map(|p| p.manhattan_distance(Point3::ORIGIN))
+                .sum::<usize>(),
+            6
+        );
+    }
+}
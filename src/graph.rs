@@ -0,0 +1,212 @@
+//! Shortest path algorithms over any graph which can list the neighbours of a node.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+/// A directed graph with weighted edges.
+pub trait Graph {
+    type Node: Copy + Eq + Hash;
+
+    /// returns the nodes reachable in a single step, with the cost of that step
+    fn neighbours(&self, node: Self::Node) -> impl Iterator<Item = (Self::Node, usize)>;
+}
+
+/// A graph whose nodes are numbered from 0 up to `node_count`.
+pub trait IndexedGraph: Graph<Node = usize> {
+    fn node_count(&self) -> usize;
+}
+
+/// returns the amount of steps from the nearest start to every reachable node, ignoring costs
+pub fn bfs<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+) -> HashMap<G::Node, usize> {
+    let mut distances = HashMap::new();
+    let mut to_visit = VecDeque::new();
+
+    for start in starts {
+        if distances.insert(start, 0).is_none() {
+            to_visit.push_back(start);
+        }
+    }
+
+    while let Some(node) = to_visit.pop_front() {
+        let distance = distances[&node];
+        for (neighbour, _) in graph.neighbours(node) {
+            if let Entry::Vacant(entry) = distances.entry(neighbour) {
+                entry.insert(distance + 1);
+                to_visit.push_back(neighbour);
+            }
+        }
+    }
+
+    distances
+}
+
+/// returns the cost of the cheapest path from the nearest start to every reachable node
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::Node>,
+) -> HashMap<G::Node, usize>
+where
+    G::Node: Ord,
+{
+    let mut distances = HashMap::new();
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        distances.insert(start, 0);
+        queue.push(Reverse((0, start)));
+    }
+
+    while let Some(Reverse((distance, node))) = queue.pop() {
+        if distances.get(&node).is_some_and(|best| *best < distance) {
+            // a cheaper path to the node was already handled
+            continue;
+        }
+
+        for (neighbour, cost) in graph.neighbours(node) {
+            let new_distance = distance + cost;
+            if distances
+                .get(&neighbour)
+                .is_none_or(|best| new_distance < *best)
+            {
+                distances.insert(neighbour, new_distance);
+                queue.push(Reverse((new_distance, neighbour)));
+            }
+        }
+    }
+
+    distances
+}
+
+/// returns the cost of the cheapest path from `start` to `goal`,
+/// `heuristic` estimates the remaining cost from a node and should never overestimate it
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    goal: G::Node,
+    heuristic: impl Fn(G::Node) -> usize,
+) -> Option<usize>
+where
+    G::Node: Ord,
+{
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = BinaryHeap::from([Reverse((heuristic(start), 0, start))]);
+
+    while let Some(Reverse((_, distance, node))) = queue.pop() {
+        if node == goal {
+            return Some(distance);
+        }
+        if distances[&node] < distance {
+            continue;
+        }
+
+        for (neighbour, cost) in graph.neighbours(node) {
+            let new_distance = distance + cost;
+            if distances
+                .get(&neighbour)
+                .is_none_or(|best| new_distance < *best)
+            {
+                distances.insert(neighbour, new_distance);
+                queue.push(Reverse((
+                    new_distance + heuristic(neighbour),
+                    new_distance,
+                    neighbour,
+                )));
+            }
+        }
+    }
+
+    None
+}
+
+/// returns the cost of the cheapest path between every pair of nodes, `None` if unreachable
+pub fn floyd_warshall<G: IndexedGraph>(graph: &G) -> Vec<Vec<Option<usize>>> {
+    let node_count = graph.node_count();
+    let mut distances = vec![vec![None; node_count]; node_count];
+
+    for (node, row) in distances.iter_mut().enumerate() {
+        row[node] = Some(0);
+        for (neighbour, cost) in graph.neighbours(node) {
+            if row[neighbour].is_none_or(|best| cost < best) {
+                row[neighbour] = Some(cost);
+            }
+        }
+    }
+
+    for via in 0..node_count {
+        let from_via = distances[via].clone();
+
+        for row in distances.iter_mut() {
+            let Some(to_via) = row[via] else {
+                continue;
+            };
+
+            for (best, from_via) in row.iter_mut().zip(&from_via) {
+                if let Some(from_via) = from_via {
+                    let distance = to_via + from_via;
+                    if best.is_none_or(|best| distance < best) {
+                        *best = Some(distance);
+                    }
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -1-> 1 -1-> 2 -1-> 3 and a shortcut 0 -5-> 3, 4 is unreachable
+    struct Line;
+
+    impl Graph for Line {
+        type Node = usize;
+
+        fn neighbours(&self, node: usize) -> impl Iterator<Item = (usize, usize)> {
+            let shortcut = (node == 0).then_some((3, 5));
+            let next = (node < 3).then_some((node + 1, 1));
+
+            shortcut.into_iter().chain(next)
+        }
+    }
+
+    impl IndexedGraph for Line {
+        fn node_count(&self) -> usize {
+            5
+        }
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let distances = bfs(&Line, [0]);
+
+        assert_eq!(distances[&3], 1);
+        assert_eq!(distances.get(&4), None);
+        assert_eq!(bfs(&Line, [0, 2])[&2], 0);
+    }
+
+    #[test]
+    fn dijkstra_and_astar_follow_costs() {
+        assert_eq!(dijkstra(&Line, [0])[&3], 3);
+        assert_eq!(astar(&Line, 0, 3, |node| 3 - node.min(3)), Some(3));
+        assert_eq!(astar(&Line, 0, 4, |_| 0), None);
+    }
+
+    #[test]
+    fn floyd_warshall_finds_all_pairs() {
+        let distances = floyd_warshall(&Line);
+
+        assert_eq!(distances[0][3], Some(3));
+        assert_eq!(distances[1][3], Some(2));
+        assert_eq!(distances[3][0], None);
+        assert_eq!(distances[4][4], Some(0));
+    }
+}
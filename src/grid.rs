@@ -0,0 +1,354 @@
+//! Two-dimensional grids of cells, parsed from character maps or filled while solving.
+
+use std::{collections::HashMap, ops::Index};
+
+use itertools::Either;
+
+use crate::{
+    error,
+    geom::{Direction, Direction8, Point2},
+    ParseError,
+};
+
+/// (x, y) of a cell, x grows to the right and y grows downwards in a parsed map
+pub type Position = (usize, usize);
+
+/// A rectangle of `width` by `height` cells.
+///
+/// A dense grid stores every cell, a sparse grid only stores the cells which were inserted
+/// and returns a background value for every other cell within its bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Cells<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Cells<T> {
+    Dense(Vec<T>),
+    Sparse {
+        cells: HashMap<Position, T>,
+        background: T,
+    },
+}
+
+impl<T> Grid<T> {
+    /// a dense grid of the cells row by row, `cells` should contain `width * height` cells
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "A grid of {}x{} needs {} cells",
+            width,
+            height,
+            width * height
+        );
+
+        Grid {
+            width,
+            height,
+            cells: Cells::Dense(cells),
+        }
+    }
+
+    /// an empty sparse grid, which grows when cells are inserted outside of its bounds
+    pub fn sparse(width: usize, height: usize, background: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: Cells::Sparse {
+                cells: HashMap::new(),
+                background,
+            },
+        }
+    }
+
+    /// parses a dense grid from a map with a character per cell, every line is a row
+    pub fn parse(
+        day: u8,
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let width = input.lines().next().map_or(0, |line| line.chars().count());
+        let mut cells = vec![];
+        let mut height = 0;
+
+        for line in error::lines(day, input) {
+            if line.text.chars().count() != width || width == 0 {
+                return Err(line.error(line.text, format!("a row of {} cells", width)));
+            }
+
+            for (index, c) in line.text.char_indices() {
+                cells.push(cell(c).ok_or_else(|| line.error(&line.text[index..], expected))?);
+            }
+            height += 1;
+        }
+
+        if height == 0 {
+            return Err(ParseError::new(day, input, "a map of at least one row"));
+        }
+
+        Ok(Grid::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_sparse(&self) -> bool {
+        matches!(self.cells, Cells::Sparse { .. })
+    }
+
+    /// returns true if the position lies within the bounds of the grid
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// returns the cell at the position, or `None` outside of the bounds
+    pub fn get(&self, position: Position) -> Option<&T> {
+        if !self.contains(position) {
+            return None;
+        }
+
+        match &self.cells {
+            Cells::Dense(cells) => Some(&cells[position.1 * self.width + position.0]),
+            Cells::Sparse { cells, background } => Some(cells.get(&position).unwrap_or(background)),
+        }
+    }
+
+    /// replaces the cell at the position,
+    /// a sparse grid grows to contain it while a dense grid panics outside of its bounds
+    pub fn insert(&mut self, position: Position, value: T) {
+        match &mut self.cells {
+            Cells::Dense(cells) => {
+                assert!(
+                    position.0 < self.width && position.1 < self.height,
+                    "Position {:?} is outside of the {}x{} grid",
+                    position,
+                    self.width,
+                    self.height
+                );
+                cells[position.1 * self.width + position.0] = value;
+            }
+            Cells::Sparse { cells, .. } => {
+                self.width = self.width.max(position.0 + 1);
+                self.height = self.height.max(position.1 + 1);
+                cells.insert(position, value);
+            }
+        }
+    }
+
+    /// returns the position `offset` away, if it lies within the bounds
+    pub fn step(&self, position: Position, offset: Point2) -> Option<Position> {
+        let position = (Point2::from(position) + offset).to_position()?;
+
+        self.contains(position).then_some(position)
+    }
+
+    /// returns the orthogonal neighbours of the position within the bounds
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction.offset()))
+    }
+
+    /// returns the orthogonal and diagonal neighbours of the position within the bounds
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction8::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction.offset()))
+    }
+
+    /// returns the positions from `from` in the direction of `offset` till the edge, without `from`
+    pub fn ray(&self, from: Position, offset: Point2) -> impl Iterator<Item = Position> + '_ {
+        std::iter::successors(self.step(from, offset), move |position| {
+            self.step(*position, offset)
+        })
+    }
+
+    /// returns every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// returns every position with its cell, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.positions()
+            .map(move |position| (position, &self[position]))
+    }
+
+    /// returns the cells which are stored with their position, every cell of a dense grid but
+    /// only the inserted cells of a sparse grid, in no particular order
+    pub fn stored(&self) -> impl Iterator<Item = (Position, &T)> + '_ {
+        match &self.cells {
+            Cells::Dense(_) => Either::Left(self.iter()),
+            Cells::Sparse { cells, .. } => {
+                Either::Right(cells.iter().map(|(position, cell)| (*position, cell)))
+            }
+        }
+    }
+
+    /// returns the cells of a row from left to right
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        (0..self.width).map(move |x| &self[(x, y)])
+    }
+
+    /// returns the cells of a column from top to bottom
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        (0..self.height).map(move |y| &self[(x, y)])
+    }
+
+    /// returns every row, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + '_> + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// returns every column, from left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// returns a grid with the same backing where every cell is mapped by `f`
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        let cells = match &self.cells {
+            Cells::Dense(cells) => Cells::Dense(cells.iter().map(f).collect()),
+            Cells::Sparse { cells, background } => Cells::Sparse {
+                cells: cells
+                    .iter()
+                    .map(|(position, cell)| (*position, f(cell)))
+                    .collect(),
+                background: f(background),
+            },
+        };
+
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// a dense grid where every cell has the same value
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid::new(width, height, vec![value; width * height])
+    }
+
+    /// returns the grid mirrored over its diagonal, rows become columns
+    pub fn transpose(&self) -> Self {
+        let cells = match &self.cells {
+            Cells::Dense(_) => Cells::Dense(self.columns().flatten().cloned().collect()),
+            Cells::Sparse { cells, background } => Cells::Sparse {
+                cells: cells
+                    .iter()
+                    .map(|((x, y), cell)| ((*y, *x), cell.clone()))
+                    .collect(),
+                background: background.clone(),
+            },
+        };
+
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// returns a dense grid with the same cells
+    pub fn to_dense(&self) -> Self {
+        Grid::new(
+            self.width,
+            self.height,
+            self.iter().map(|(_, cell)| cell.clone()).collect(),
+        )
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "Position {:?} is outside of the {}x{} grid",
+                position, self.width, self.height
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Grid<u32> {
+        Grid::parse(0, "123\n456\n", "a digit", |c| c.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn parses_character_map() {
+        let grid = digits();
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+
+        let error = Grid::parse(0, "123\n4x6\n", "a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+    }
+
+    #[test]
+    fn finds_neighbours_within_bounds() {
+        let grid = digits();
+
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<Position>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn iterates_rows_columns_and_rays() {
+        let grid = digits();
+
+        assert_eq!(grid.row(1).copied().collect::<Vec<u32>>(), vec![4, 5, 6]);
+        assert_eq!(
+            grid.column(2).rev().copied().collect::<Vec<u32>>(),
+            vec![6, 3]
+        );
+        assert_eq!(
+            grid.ray((0, 0), Direction::Right.offset())
+                .collect::<Vec<Position>>(),
+            vec![(1, 0), (2, 0)]
+        );
+        assert_eq!(
+            grid.transpose().row(2).copied().collect::<Vec<u32>>(),
+            vec![3, 6]
+        );
+    }
+
+    #[test]
+    fn sparse_grid_grows() {
+        let mut grid = Grid::sparse(2, 0, '.');
+        grid.insert((4, 1), '#');
+
+        assert_eq!((grid.width(), grid.height()), (5, 2));
+        assert_eq!(grid[(4, 1)], '#');
+        assert_eq!(grid[(0, 0)], '.');
+        assert_eq!(grid.transpose()[(1, 4)], '#');
+        assert_eq!(grid.to_dense(), {
+            let mut dense = Grid::filled(5, 2, '.');
+            dense.insert((4, 1), '#');
+            dense
+        });
+    }
+}
@@ -0,0 +1,35 @@
+//! A stable hash, the same across runs, builds and platforms.
+//!
+//! This module has no dependencies, `build.rs` includes it to hash the sources of the solvers.
+
+use std::hash::Hasher;
+
+/// The 64 bit FNV-1a hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fnv(u64);
+
+impl Default for Fnv {
+    fn default() -> Self {
+        Fnv(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// returns the hash of the bytes in hex
+pub fn hex(bytes: &[u8]) -> String {
+    let mut hasher = Fnv::default();
+    hasher.write(bytes);
+
+    format!("{:016x}", hasher.finish())
+}
@@ -0,0 +1,244 @@
+//! The ledger of produced and submitted answers, `answers.json`.
+//!
+//! Every answer the runner produced is recorded once per day, part and input, together with what
+//! the server said about it. Answers of an input are only compared with answers of the same input.
+
+use std::{
+    fmt, fs, io,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash;
+
+pub const LEDGER_PATH: &str = "answers.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ledger {
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    /// seconds since the unix epoch of the last change of the status
+    pub timestamp: u64,
+    /// `hash_input` of the input the answer was produced for
+    pub input_hash: String,
+    pub status: Status,
+}
+
+/// What the server said about an answer.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Unsubmitted,
+    Accepted,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+/// A reason to doubt a new answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// the answer was already submitted and rejected
+    Rejected(Status),
+    /// the answer is not below an answer that was too high
+    AboveBound(i64),
+    /// the answer is not above an answer that was too low
+    BelowBound(i64),
+}
+
+/// returns a stable hash of an input, the FNV-1a hash of its bytes in hex
+pub fn hash_input(input: &str) -> String {
+    hash::hex(input.as_bytes())
+}
+
+/// returns the seconds since the unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+impl Ledger {
+    /// reads the ledger, an empty ledger if the file does not exist yet
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(ledger) => serde_json::from_str(&ledger).map_err(io::Error::from),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+    }
+
+    /// returns the entries of `part` of `day` for the input
+    pub fn entries<'a>(
+        &'a self,
+        day: u8,
+        part: u8,
+        input_hash: &'a str,
+    ) -> impl Iterator<Item = &'a Entry> + 'a {
+        self.entries.iter().filter(move |entry| {
+            entry.day == day && entry.part == part && entry.input_hash == input_hash
+        })
+    }
+
+    /// adds the answer as unsubmitted, unless it is already known
+    pub fn record(&mut self, day: u8, part: u8, input_hash: &str, answer: &str) -> &mut Entry {
+        let index = match self.entries.iter().position(|entry| {
+            entry.day == day
+                && entry.part == part
+                && entry.input_hash == input_hash
+                && entry.answer == answer
+        }) {
+            Some(index) => index,
+            None => {
+                self.entries.push(Entry {
+                    day,
+                    part,
+                    answer: answer.to_string(),
+                    timestamp: now(),
+                    input_hash: input_hash.to_string(),
+                    status: Status::Unsubmitted,
+                });
+                self.entries.len() - 1
+            }
+        };
+
+        &mut self.entries[index]
+    }
+
+    /// returns the reasons to doubt an answer, given the earlier answers for the input
+    pub fn check(&self, day: u8, part: u8, input_hash: &str, answer: &str) -> Vec<Warning> {
+        let mut warnings = vec![];
+        let value = answer.trim().parse::<i64>().ok();
+
+        for entry in self.entries(day, part, input_hash) {
+            if entry.answer == answer {
+                if entry.status.is_rejected() {
+                    warnings.push(Warning::Rejected(entry.status));
+                }
+                continue;
+            }
+
+            let (Some(value), Ok(bound)) = (value, entry.answer.trim().parse::<i64>()) else {
+                continue;
+            };
+            match entry.status {
+                Status::TooHigh if value >= bound => warnings.push(Warning::AboveBound(bound)),
+                Status::TooLow if value <= bound => warnings.push(Warning::BelowBound(bound)),
+                _ => {}
+            }
+        }
+
+        warnings
+    }
+}
+
+impl Entry {
+    pub fn set_status(&mut self, status: Status) {
+        self.status = status;
+        self.timestamp = now();
+    }
+}
+
+impl Status {
+    pub fn is_rejected(self) -> bool {
+        matches!(self, Status::TooHigh | Status::TooLow | Status::Wrong)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Unsubmitted => write!(f, "unsubmitted"),
+            Status::Accepted => write!(f, "accepted"),
+            Status::TooHigh => write!(f, "too high"),
+            Status::TooLow => write!(f, "too low"),
+            Status::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::Rejected(status) => {
+                write!(f, "this answer was already rejected as {}", status)
+            }
+            Warning::AboveBound(bound) => write!(f, "{} was already too high", bound),
+            Warning::BelowBound(bound) => write!(f, "{} was already too low", bound),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger() -> Ledger {
+        let mut ledger = Ledger::default();
+        for (answer, status) in [
+            ("100", Status::TooHigh),
+            ("10", Status::TooLow),
+            ("42", Status::Wrong),
+        ] {
+            ledger.record(1, 1, "abc", answer).set_status(status);
+        }
+        ledger
+    }
+
+    #[test]
+    fn records_an_answer_once() {
+        let mut ledger = ledger();
+        ledger.record(1, 1, "abc", "50");
+        ledger.record(1, 1, "abc", "50");
+        ledger.record(1, 1, "abc", "42");
+
+        assert_eq!(ledger.entries.len(), 4);
+        assert_eq!(ledger.entries[2].status, Status::Wrong);
+        assert_eq!(ledger.entries[3].status, Status::Unsubmitted);
+    }
+
+    #[test]
+    fn warns_about_rejected_answers_and_bounds() {
+        let ledger = ledger();
+
+        assert_eq!(ledger.check(1, 1, "abc", "50"), vec![]);
+        assert_eq!(
+            ledger.check(1, 1, "abc", "42"),
+            vec![Warning::Rejected(Status::Wrong)]
+        );
+        assert_eq!(
+            ledger.check(1, 1, "abc", "100"),
+            vec![Warning::Rejected(Status::TooHigh)]
+        );
+        assert_eq!(
+            ledger.check(1, 1, "abc", "150"),
+            vec![Warning::AboveBound(100)]
+        );
+        assert_eq!(
+            ledger.check(1, 1, "abc", "3"),
+            vec![Warning::BelowBound(10)]
+        );
+        // other parts and inputs have other answers
+        assert_eq!(ledger.check(1, 2, "abc", "42"), vec![]);
+        assert_eq!(ledger.check(1, 1, "def", "42"), vec![]);
+    }
+
+    #[test]
+    fn hashes_are_stable() {
+        assert_eq!(hash_input(""), "cbf29ce484222325");
+        assert_eq!(hash_input("a"), "af63dc4c8601ec8c");
+        assert_ne!(hash_input("1\n2\n"), hash_input("1\n3\n"));
+    }
+}
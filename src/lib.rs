@@ -1 +1,66 @@
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod error;
+pub mod fetch;
+pub mod gen;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod hash;
+pub mod ledger;
+pub mod params;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod viz;
+pub mod watch;
 
+pub use error::ParseError;
+pub use grid::Grid;
+pub use solution::{Maybe, Solution, SolutionSolver, Solver};
+
+/// every solved day, in order
+pub static SOLVERS: &[&dyn Solver] = &[
+    &SolutionSolver::<day1::Day1>::new(),
+    &SolutionSolver::<day2::Day2>::new(),
+    &SolutionSolver::<day3::Day3>::new(),
+    &SolutionSolver::<day4::Day4>::new(),
+    &SolutionSolver::<day5::Day5>::new(),
+    &SolutionSolver::<day6::Day6>::new(),
+    &SolutionSolver::<day7::Day7>::new(),
+    &SolutionSolver::<day8::Day8>::new(),
+    &SolutionSolver::<day9::Day9>::new(),
+    &SolutionSolver::<day10::Day10>::new(),
+    &SolutionSolver::<day11::Day11>::new(),
+    &SolutionSolver::<day12::Day12>::new(),
+    &SolutionSolver::<day13::Day13>::new(),
+    &SolutionSolver::<day14::Day14>::new(),
+    &SolutionSolver::<day15::Day15>::new(),
+    &SolutionSolver::<day16::Day16>::new(),
+    &SolutionSolver::<day17::Day17>::new(),
+    &SolutionSolver::<day18::Day18>::new(),
+];
+
+/// returns the solver of a day
+pub fn solver(day: u8) -> Option<&'static dyn Solver> {
+    SOLVERS.iter().copied().find(|solver| solver.day() == day)
+}
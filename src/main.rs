@@ -0,0 +1,1034 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context, Result};
+use aoc::{
+    answers::Manifest,
+    bench::{self, Benchmark},
+    cache::{self, Cache},
+    day1::{self, Day1, TopK},
+    day2::{self, Player, Rules},
+    fetch::{self, Fetched},
+    gen::{self, Rng},
+    ledger::{self, Ledger, Status},
+    params,
+    runner::{self, Failure, Job, Outcome, Report},
+    scaffold, viz, watch, Solution,
+};
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use itertools::Itertools;
+use serde::Serialize;
+use tracing::{info, warn, Level};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Logs what the solvers do on stderr, -v shows debug events and -vv trace events
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the solver of a single day, or of every day with --all
+    Run {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25), required_unless_present = "all")]
+        day: Option<u8>,
+        /// Part to solve, both parts are solved when omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input, `-` reads from stdin (default: data/day<DAY>.input)
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Solves every day on its default input and checks the answers against answers.toml
+        #[arg(long, conflicts_with_all = ["day", "input"])]
+        all: bool,
+        /// Amount of threads used by --all (default: available parallelism)
+        #[arg(long, requires = "all", value_parser = clap::value_parser!(u64).range(1..))]
+        jobs: Option<u64>,
+        /// `json` prints a list of `{day, part, answer, elapsed_ms}` objects instead
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Overrides a parameter of the day, after the overrides of aoc.toml
+        #[arg(long, value_name = "NAME=VALUE", value_parser = params::parse_assignment, conflicts_with = "all")]
+        set: Vec<(String, i64)>,
+        /// Solves again instead of using the answers cached in .aoc-cache, and caches the new ones
+        #[arg(long)]
+        no_cache: bool,
+        /// Reads the input line by line in constant memory, without the cache and the ledger,
+        /// only day 1 can stream its input
+        #[arg(long, conflicts_with_all = ["all", "no_cache"])]
+        stream: bool,
+    },
+    /// Solves a day on its test files whenever one of its files in data/ changes
+    #[cfg(target_os = "linux")]
+    Watch {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Also solves the .input files of the day
+        #[arg(long)]
+        input: bool,
+    },
+    /// Manages the answers cached by run
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Downloads the puzzle input of a day into data/day<DAY>.input, unless it is already there
+    Fetch {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Solves a part of a day on its default input and submits the answer, unless it is known to be wrong
+    Submit {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Submits the answer even if the ledger says it is wrong
+        #[arg(long)]
+        force: bool,
+    },
+    /// Creates the solver module, input files and answer stubs of a new day
+    New {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Prints statistics of the puzzle input of a day, like the calories of every elf of day 1
+    /// or the scores of every reading of the strategy guide of day 2
+    Report {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Puzzle input, `-` reads from stdin (default: data/day<DAY>.input)
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Only reports the first N rows, like the N elves carrying the most calories
+        /// or the N decodings of the guide of day 2 scoring the most
+        #[arg(long, value_name = "N")]
+        top: Option<usize>,
+        /// Only breaks down the first N rounds of the guide of day 2, one row per round
+        /// (default: every round)
+        #[arg(long, value_name = "N")]
+        rounds: Option<usize>,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Animates the simulation of day 9, 10, 14 or 17 in the terminal
+    Viz {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Puzzle input (default: data/day<DAY>.input)
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Steps of the simulation per second, + and - change it while playing
+        #[arg(long, default_value_t = 20.0)]
+        speed: f64,
+    },
+    /// Plays a round-robin tournament between strategies of day 2 and prints the standings
+    Tournament {
+        /// Rounds of every match
+        #[arg(long, default_value_t = 1000)]
+        rounds: usize,
+        /// Seed of the random player
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Strategy guide of the player following it, read as in part 2 (default: data/day2.input)
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Plays rock paper scissors lizard Spock instead of rock paper scissors
+        #[arg(long)]
+        lizard_spock: bool,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Writes a random input for a day, the same one for the same size and seed
+    Gen {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// What it counts depends on the day, like the moves of day 9 or the width of the grid of day 8
+        #[arg(long, default_value_t = 1000)]
+        size: usize,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// File to write the input to (default: stdout)
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Times parsing, part 1 and part 2 of every day over repeated runs
+    Bench {
+        /// Only benchmark this day
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Puzzle input, only used together with --day (default: data/day<DAY>.input)
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Benchmarks inputs generated with these sizes instead and plots the time against the size,
+        /// like 1000,10000,100000
+        #[arg(
+            long,
+            value_delimiter = ',',
+            requires = "day",
+            conflicts_with = "input"
+        )]
+        sizes: Vec<usize>,
+        /// Seed of the inputs generated for --sizes
+        #[arg(long, default_value_t = 0, requires = "sizes")]
+        seed: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Lists the cached answers
+    Ls,
+    /// Removes the cached answers of a day, or of every day
+    Clear {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    tracing_subscriber::fmt()
+        .with_max_level(match cli.verbose {
+            0 => Level::INFO,
+            1 => Level::DEBUG,
+            _ => Level::TRACE,
+        })
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .with_target(false)
+        .without_time()
+        .init();
+
+    match cli.command {
+        Command::Run {
+            day: Some(day),
+            part,
+            input,
+            format,
+            set,
+            stream: true,
+            ..
+        } => run_streaming(day, part, input, format, &set),
+        Command::Run {
+            day: Some(day),
+            part,
+            input,
+            format,
+            set,
+            no_cache,
+            ..
+        } => run(day, part, input, format, &set, no_cache),
+        Command::Run {
+            part,
+            jobs,
+            format,
+            no_cache,
+            ..
+        } => run_all(part, jobs, format, no_cache),
+        #[cfg(target_os = "linux")]
+        Command::Watch { day, input } => watch(day, input),
+        Command::Cache { command } => manage_cache(command),
+        Command::Fetch { day } => fetch(day),
+        Command::Submit { day, part, force } => submit(day, part, force),
+        Command::New { day } => new(day),
+        Command::Report {
+            day,
+            input,
+            top,
+            rounds,
+            format,
+        } => report(day, input, top, rounds, format),
+        Command::Viz {
+            day,
+            part,
+            input,
+            speed,
+        } => visualize(day, part, input, speed),
+        Command::Tournament {
+            rounds,
+            seed,
+            input,
+            lizard_spock,
+            format,
+        } => tournament(rounds, seed, input, lizard_spock, format),
+        Command::Gen {
+            day,
+            size,
+            seed,
+            output,
+        } => generate(day, size, seed, output),
+        Command::Bench {
+            day: Some(day),
+            runs,
+            format,
+            sizes,
+            seed,
+            ..
+        } if !sizes.is_empty() => run_scaling_bench(day, &sizes, seed, runs, format),
+        Command::Bench {
+            day,
+            input,
+            runs,
+            format,
+            ..
+        } => run_bench(day, input, runs, format),
+    }
+}
+
+fn run(
+    day: u8,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    format: Format,
+    set: &[(String, i64)],
+    no_cache: bool,
+) -> Result<()> {
+    let Some(solver) = aoc::solver(day) else {
+        bail!("Day {} is not solved yet", day);
+    };
+
+    let input_path = match input {
+        Some(input_path) => input_path,
+        None => {
+            let input_path = runner::default_input(day);
+            if !input_path.exists() {
+                offer_fetch(day, &input_path)?;
+            }
+            input_path
+        }
+    };
+    let mut params = load_params()?.params(solver, &input_path)?;
+    for (name, value) in set {
+        params.set(name, *value)?;
+    }
+
+    let input = read_input(&input_path)?;
+    let input_hash = ledger::hash_input(&input);
+    let cache = Cache::new(cache::CACHE_DIR);
+    let key = Cache::key(day, &input, &params);
+    // only parsed once an answer is not cached
+    let mut parsed = None;
+
+    let ledger_path = Path::new(ledger::LEDGER_PATH);
+    let mut ledger = Ledger::load(ledger_path).context("Could not read the answer ledger")?;
+
+    let mut reports = vec![];
+    for current_part in 1..=2 {
+        if part.is_some_and(|part| part != current_part) {
+            continue;
+        }
+
+        let entry = match no_cache {
+            true => None,
+            false => cache.get(day, current_part, &key),
+        };
+        let (answer, elapsed, cached) = match entry {
+            Some(entry) => {
+                info!(
+                    part = current_part,
+                    "the answer is cached, --no-cache solves again"
+                );
+                (entry.answer, Duration::ZERO, true)
+            }
+            None => {
+                // the parse time counts towards the first part solved only
+                let now = Instant::now();
+                if parsed.is_none() {
+                    parsed = Some(solver.parse(&input)?);
+                }
+
+                let answer = solver.solve(
+                    parsed.as_deref().expect("The input is parsed"),
+                    current_part,
+                    &params,
+                );
+                (answer, now.elapsed(), false)
+            }
+        };
+
+        let outcome = Outcome {
+            day,
+            part: current_part,
+            answer: Ok(answer.clone()),
+            elapsed,
+            cached,
+        };
+        if !cached {
+            cache_answer(&cache, &key, &outcome);
+        }
+        match format {
+            Format::Text => println!("Part {}: {}", current_part, answer),
+            Format::Json => reports.push(Report::from(&outcome)),
+        }
+
+        for warning in ledger.check(day, current_part, &input_hash, &answer) {
+            eprintln!("warning: part {}: {}", current_part, warning);
+        }
+        ledger.record(day, current_part, &input_hash, &answer);
+    }
+
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+
+    ledger
+        .save(ledger_path)
+        .context("Could not write the answer ledger")
+}
+
+/// solves day 1 without holding its input in memory, the elves carrying the most calories
+/// are kept in a heap while their totals are read
+fn run_streaming(
+    day: u8,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    format: Format,
+    set: &[(String, i64)],
+) -> Result<()> {
+    if day != Day1::DAY {
+        bail!("Day {} can not stream its input, only day 1 can", day);
+    }
+
+    let input_path = input.unwrap_or_else(|| runner::default_input(day));
+    let solver = aoc::solver(day).expect("Day 1 is solved");
+    let mut params = load_params()?.params(solver, &input_path)?;
+    for (name, value) in set {
+        params.set(name, *value)?;
+    }
+    let top_elves = params.get::<usize>("top_elves");
+
+    let reader: Box<dyn BufRead> = match input_path.as_os_str() == "-" {
+        true => Box::new(io::stdin().lock()),
+        false => Box::new(BufReader::new(File::open(&input_path).with_context(
+            || format!("Could not read input {}", input_path.display()),
+        )?)),
+    };
+
+    let now = Instant::now();
+    let top = itertools::process_results(reader.lines(), |lines| {
+        itertools::process_results(day1::totals(lines), |totals| totals.top_k(top_elves.max(1)))
+    })
+    .context("Could not read the input")??;
+    let elapsed = now.elapsed();
+    if top.is_empty() {
+        bail!("The input has no elf carrying calories");
+    }
+
+    let mut reports = vec![];
+    for current_part in 1..=2 {
+        if part.is_some_and(|part| part != current_part) {
+            continue;
+        }
+
+        let answer = match current_part {
+            1 => top[0],
+            _ => top.iter().take(top_elves).sum(),
+        };
+        let outcome = Outcome {
+            day,
+            part: current_part,
+            answer: Ok(answer.to_string()),
+            elapsed,
+            cached: false,
+        };
+        match format {
+            Format::Text => println!("Part {}: {}", current_part, answer),
+            Format::Json => reports.push(Report::from(&outcome)),
+        }
+    }
+
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+
+    Ok(())
+}
+
+fn run_all(part: Option<u8>, jobs: Option<u64>, format: Format, no_cache: bool) -> Result<()> {
+    let manifest = fs::read_to_string("answers.toml").context("Could not read answers.toml")?;
+    let manifest = Manifest::parse(&manifest).context("Could not parse answers.toml")?;
+    let config = load_params()?;
+    let threads = match jobs {
+        Some(jobs) => jobs as usize,
+        None => thread::available_parallelism().map_or(1, usize::from),
+    };
+
+    let cache = Cache::new(cache::CACHE_DIR);
+    let mut keys = HashMap::new();
+
+    let mut jobs = vec![];
+    // outcomes known without solving
+    let mut known = vec![];
+    for solver in aoc::SOLVERS {
+        let parts = (1..=2).filter(|current_part| part.is_none_or(|part| part == *current_part));
+
+        let input_path = runner::default_input(solver.day());
+        let params = config.params(*solver, &input_path)?;
+
+        match fs::read_to_string(input_path) {
+            Ok(input) => {
+                let key = Cache::key(solver.day(), &input, &params);
+                let input: Arc<str> = input.into();
+
+                for part in parts {
+                    let cached = match no_cache {
+                        true => None,
+                        false => cache.get(solver.day(), part, &key),
+                    };
+
+                    match cached {
+                        Some(entry) => known.push(Outcome {
+                            day: solver.day(),
+                            part,
+                            answer: Ok(entry.answer),
+                            elapsed: Duration::ZERO,
+                            cached: true,
+                        }),
+                        None => jobs.push(Job {
+                            solver: *solver,
+                            part,
+                            input: input.clone(),
+                            params: params.clone(),
+                        }),
+                    }
+                }
+                keys.insert(solver.day(), key);
+            }
+            Err(error) => known.extend(parts.map(|part| Outcome {
+                day: solver.day(),
+                part,
+                answer: Err(Failure::Input(error.to_string())),
+                elapsed: Duration::ZERO,
+                cached: false,
+            })),
+        }
+    }
+
+    let mut outcomes = runner::run_all(jobs, threads);
+    for outcome in &outcomes {
+        cache_answer(&cache, &keys[&outcome.day], outcome);
+    }
+    outcomes.extend(known);
+    outcomes.sort_by_key(|outcome| (outcome.day, outcome.part));
+
+    let mut failures = 0;
+    let mut rows = vec![];
+    for outcome in &outcomes {
+        let expected = manifest.expected(
+            outcome.day,
+            outcome.part,
+            &runner::default_input(outcome.day),
+        );
+
+        let (answer, status) = match (&outcome.answer, expected) {
+            (Ok(answer), Some(expected)) if *answer == expected.answer && outcome.cached => {
+                (answer.as_str(), "pass, cached".to_string())
+            }
+            (Ok(answer), Some(expected)) if *answer == expected.answer => {
+                (answer.as_str(), "pass".to_string())
+            }
+            (Ok(answer), Some(expected)) => {
+                failures += 1;
+                (
+                    answer.as_str(),
+                    format!("FAIL, expected {:?}", expected.answer),
+                )
+            }
+            (Ok(answer), None) if outcome.cached => {
+                (answer.as_str(), "unknown, cached".to_string())
+            }
+            (Ok(answer), None) => (answer.as_str(), "unknown".to_string()),
+            (Err(failure), _) => {
+                failures += 1;
+                ("", format!("FAIL, {}", failure))
+            }
+        };
+        rows.push((outcome, answer, status));
+    }
+
+    match format {
+        Format::Text => print_outcomes(&rows),
+        Format::Json => {
+            let reports = outcomes.iter().map(Report::from).collect::<Vec<Report>>();
+            println!("{}", serde_json::to_string_pretty(&reports)?);
+        }
+    }
+
+    if failures > 0 {
+        bail!("{} of {} answers failed", failures, outcomes.len());
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn watch(day: u8, with_input: bool) -> Result<()> {
+    let Some(solver) = aoc::solver(day) else {
+        bail!("Day {} is not solved yet", day);
+    };
+
+    let data = Path::new("data");
+    let mut watcher = watch::Watcher::new(data, day).context("Could not watch data/")?;
+    let threads = thread::available_parallelism().map_or(1, usize::from);
+
+    for run in 1.. {
+        let manifest = fs::read_to_string("answers.toml").context("Could not read answers.toml")?;
+        let manifest = Manifest::parse(&manifest).context("Could not parse answers.toml")?;
+        let config = load_params()?;
+
+        let mut jobs = vec![];
+        let mut results = vec![];
+        for input_path in watch::inputs(data, day, with_input)? {
+            let params = config.params(solver, &input_path)?;
+
+            match fs::read_to_string(&input_path) {
+                Ok(input) => {
+                    let input: Arc<str> = input.into();
+                    jobs.extend((1..=2).map(|part| Job {
+                        solver,
+                        part,
+                        input: input.clone(),
+                        params: params.clone(),
+                    }));
+                    results.push((input_path, vec![]));
+                }
+                Err(error) => {
+                    let outcomes = (1..=2)
+                        .map(|part| Outcome {
+                            day,
+                            part,
+                            answer: Err(Failure::Input(error.to_string())),
+                            elapsed: Duration::ZERO,
+                            cached: false,
+                        })
+                        .collect();
+                    results.push((input_path, outcomes));
+                }
+            }
+        }
+
+        // the outcomes are in the order of the jobs, both parts of every readable input
+        let mut outcomes = runner::run_all(jobs, threads).into_iter();
+        for (_, input_outcomes) in results
+            .iter_mut()
+            .filter(|(_, outcomes)| outcomes.is_empty())
+        {
+            input_outcomes.extend(outcomes.by_ref().take(2));
+        }
+
+        let color = io::stdout().is_terminal();
+        if color {
+            // clear the screen
+            print!("\x1b[2J\x1b[H");
+        }
+        println!("day {}, run {}, watching data/day{}.*\n", day, run, day);
+        print!("{}", watch::render(day, &results, &manifest, color));
+
+        watcher.wait().context("Could not watch data/")?;
+    }
+
+    Ok(())
+}
+
+/// caches a new answer, failing to do so only warns
+fn cache_answer(cache: &Cache, key: &str, outcome: &Outcome) {
+    let Ok(answer) = &outcome.answer else {
+        return;
+    };
+
+    let elapsed_ms = outcome.elapsed.as_secs_f64() * 1000.0;
+    if let Err(error) = cache.insert(outcome.day, outcome.part, key, answer, elapsed_ms) {
+        warn!(day = outcome.day, part = outcome.part, %error, "could not cache the answer");
+    }
+}
+
+fn manage_cache(command: CacheCommand) -> Result<()> {
+    let cache = Cache::new(cache::CACHE_DIR);
+
+    match command {
+        CacheCommand::Ls => {
+            let entries = cache.entries().context("Could not read the cache")?;
+            let now = ledger::now();
+
+            println!(
+                "{:>3}  {:>4}  {:<20}  {:>12}  {:>10}  key",
+                "day", "part", "answer", "elapsed", "age"
+            );
+            for entry in entries {
+                println!(
+                    "{:>3}  {:>4}  {:<20}  {:>12}  {:>10}  {}",
+                    entry.day,
+                    entry.part,
+                    entry.answer.lines().next().unwrap_or_default(),
+                    format!("{:.2?}", Duration::from_secs_f64(entry.elapsed_ms / 1000.0)),
+                    format_age(now.saturating_sub(entry.timestamp)),
+                    entry.key
+                );
+            }
+        }
+        CacheCommand::Clear { day } => {
+            let removed = cache.clear(day).context("Could not clear the cache")?;
+            println!("Removed {} cached answers", removed);
+        }
+    }
+
+    Ok(())
+}
+
+/// formats an amount of seconds in the largest fitting unit
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m", seconds / 60),
+        3600..86400 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86400),
+    }
+}
+
+/// prints a table of outcomes with their answer and status
+fn print_outcomes(rows: &[(&Outcome, &str, String)]) {
+    let answer_width = rows
+        .iter()
+        .flat_map(|(_, answer, _)| answer.lines())
+        .map(str::len)
+        .chain(["answer".len()])
+        .max()
+        .unwrap_or_default();
+
+    println!(
+        "{:>3}  {:>4}  {:<answer_width$}  {:>12}  status",
+        "day", "part", "answer", "elapsed"
+    );
+
+    for (outcome, answer, status) in rows {
+        // multi-line answers continue below in the answer column
+        let mut lines = answer.lines();
+        println!(
+            "{:>3}  {:>4}  {:<answer_width$}  {:>12}  {}",
+            outcome.day,
+            outcome.part,
+            lines.next().unwrap_or_default(),
+            match outcome.cached {
+                true => "-".to_string(),
+                false => format!("{:.2?}", outcome.elapsed),
+            },
+            status
+        );
+        for line in lines {
+            println!("{:>3}  {:>4}  {}", "", "", line);
+        }
+    }
+}
+
+/// asks to download a missing input when running interactively
+fn offer_fetch(day: u8, input_path: &Path) -> Result<()> {
+    if !io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    eprint!("{} is missing, fetch it? [y/N] ", input_path.display());
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    if answer.trim().eq_ignore_ascii_case("y") {
+        fetch::fetch_input(&fetch::Config::load()?, day, input_path)?;
+    }
+
+    Ok(())
+}
+
+fn fetch(day: u8) -> Result<()> {
+    let input_path = runner::default_input(day);
+
+    match fetch::fetch_input(&fetch::Config::load()?, day, &input_path)? {
+        Fetched::Cached => println!("{} is already there", input_path.display()),
+        Fetched::Downloaded => println!("Downloaded {}", input_path.display()),
+    }
+
+    Ok(())
+}
+
+fn submit(day: u8, part: u8, force: bool) -> Result<()> {
+    let Some(solver) = aoc::solver(day) else {
+        bail!("Day {} is not solved yet", day);
+    };
+
+    let input_path = runner::default_input(day);
+    let params = load_params()?.params(solver, &input_path)?;
+    let input = read_input(&input_path)?;
+    let input_hash = ledger::hash_input(&input);
+    let answer = solver.solve(solver.parse(&input)?.as_ref(), part, &params);
+
+    let ledger_path = Path::new(ledger::LEDGER_PATH);
+    let mut ledger = Ledger::load(ledger_path).context("Could not read the answer ledger")?;
+
+    if let Some(accepted) = ledger
+        .entries(day, part, &input_hash)
+        .find(|entry| entry.status == Status::Accepted)
+    {
+        bail!("{} was already accepted", accepted.answer);
+    }
+    let warnings = ledger.check(day, part, &input_hash, &answer);
+    if !warnings.is_empty() && !force {
+        let warnings = warnings.iter().map(ToString::to_string).join(", ");
+        bail!("Not submitting {}: {}", answer, warnings);
+    }
+
+    let status = fetch::submit_answer(&fetch::Config::load()?, day, part, &answer)?;
+    ledger
+        .record(day, part, &input_hash, &answer)
+        .set_status(status);
+    ledger
+        .save(ledger_path)
+        .context("Could not write the answer ledger")?;
+
+    println!("{}: {}", answer, status);
+
+    Ok(())
+}
+
+fn new(day: u8) -> Result<()> {
+    if aoc::solver(day).is_some() {
+        bail!("Day {} is already solved", day);
+    }
+
+    let changed = scaffold::new_day(Path::new("."), day)
+        .with_context(|| format!("Could not create day {}", day))?;
+    for path in changed {
+        println!("{}", path.display());
+    }
+
+    Ok(())
+}
+
+fn report(
+    day: u8,
+    input: Option<PathBuf>,
+    top: Option<usize>,
+    rounds: Option<usize>,
+    format: Format,
+) -> Result<()> {
+    let input = read_input(&input.unwrap_or_else(|| runner::default_input(day)))?;
+
+    match day {
+        1 => {
+            let elves = Day1::parse(&input)?;
+            print_report(&day1::report(&elves, top.unwrap_or(elves.len())), format)
+        }
+        2 => {
+            let rules = Rules::classic();
+            let guide = day2::parse(&rules, &input)?;
+            let report = day2::report(
+                &rules,
+                &guide,
+                top.unwrap_or(usize::MAX),
+                rounds.unwrap_or(guide.len()),
+            );
+            print_report(&report, format)
+        }
+        _ => bail!("Day {} has no report", day),
+    }
+}
+
+fn tournament(
+    rounds: usize,
+    seed: u64,
+    input: Option<PathBuf>,
+    lizard_spock: bool,
+    format: Format,
+) -> Result<()> {
+    let rules = match lizard_spock {
+        true => Rules::lizard_spock(),
+        false => Rules::classic(),
+    };
+    let guide = day2::parse(
+        &rules,
+        &read_input(&input.unwrap_or_else(|| runner::default_input(2)))?,
+    )?;
+
+    let mut players: Vec<Box<dyn Player>> = rules
+        .hands()
+        .map(|hand| Box::new(day2::Fixed::new(&rules, hand)) as Box<dyn Player>)
+        .collect();
+    players.push(Box::new(day2::Random::new(seed)));
+    players.push(Box::<day2::FrequencyCounter>::default());
+    players.push(Box::<day2::WinStayLoseShift>::default());
+    players.push(Box::new(
+        day2::FollowTheGuide::new(&rules, &guide, &rules.outcome_decoding())
+            .context("The guide has letters which do not ask for an outcome")?,
+    ));
+
+    print_report(&day2::tournament(&rules, &mut players, rounds), format)
+}
+
+fn print_report(report: &(impl Serialize + Display), format: Format) -> Result<()> {
+    match format {
+        Format::Text => print!("{}", report),
+        Format::Json => println!("{}", serde_json::to_string_pretty(report)?),
+    }
+
+    Ok(())
+}
+
+fn visualize(day: u8, part: u8, input: Option<PathBuf>, speed: f64) -> Result<()> {
+    let Some(solver) = aoc::solver(day) else {
+        bail!("Day {} is not solved yet", day);
+    };
+    let input_path = input.unwrap_or_else(|| runner::default_input(day));
+    let params = load_params()?.params(solver, &input_path)?;
+
+    let Some(mut simulation) = viz::simulation(day, part, &read_input(&input_path)?, &params)?
+    else {
+        bail!("Day {} has no visualization", day);
+    };
+
+    viz::play(simulation.as_mut(), speed).context("Could not play the simulation")
+}
+
+fn generate(day: u8, size: usize, seed: u64, output: Option<PathBuf>) -> Result<()> {
+    let Some(input) = gen::generate(day, &mut Rng::new(seed), size) else {
+        bail!("Day {} has no input generator", day);
+    };
+
+    match output {
+        Some(path) => fs::write(&path, input)
+            .with_context(|| format!("Could not write input {}", path.display())),
+        None => Ok(io::stdout().lock().write_all(input.as_bytes())?),
+    }
+}
+
+fn run_bench(day: Option<u8>, input: Option<PathBuf>, runs: usize, format: Format) -> Result<()> {
+    if runs == 0 {
+        bail!("At least 1 run is needed to benchmark");
+    }
+
+    let solvers = match day {
+        Some(day) => match aoc::solver(day) {
+            Some(solver) => vec![solver],
+            None => bail!("Day {} is not solved yet", day),
+        },
+        None => aoc::SOLVERS.to_vec(),
+    };
+
+    let config = load_params()?;
+    let mut benchmarks = vec![];
+    for solver in solvers {
+        let input_path = input
+            .clone()
+            .unwrap_or_else(|| runner::default_input(solver.day()));
+        let params = config.params(solver, &input_path)?;
+        let benchmark = bench::bench(solver, &read_input(&input_path)?, &params, runs)?;
+
+        if format == Format::Text {
+            print_benchmark(&benchmark);
+        }
+        benchmarks.push(benchmark);
+    }
+
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&benchmarks)?);
+    }
+
+    Ok(())
+}
+
+fn run_scaling_bench(
+    day: u8,
+    sizes: &[usize],
+    seed: u64,
+    runs: usize,
+    format: Format,
+) -> Result<()> {
+    if runs == 0 {
+        bail!("At least 1 run is needed to benchmark");
+    }
+    let Some(solver) = aoc::solver(day) else {
+        bail!("Day {} is not solved yet", day);
+    };
+
+    // the generated inputs get the parameters of the day, without those of an input file
+    let params = load_params()?.params(solver, Path::new(""))?;
+    let mut benchmarks = vec![];
+    for size in sizes {
+        let Some(input) = gen::generate(day, &mut Rng::new(seed), *size) else {
+            bail!("Day {} has no input generator", day);
+        };
+        info!(size, bytes = input.len(), "generated input");
+
+        let mut benchmark = bench::bench(solver, &input, &params, runs)?;
+        benchmark.size = Some(*size);
+        benchmarks.push(benchmark);
+    }
+
+    match format {
+        Format::Text => print!("{}", bench::plot(&benchmarks, 40)),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&benchmarks)?),
+    }
+
+    Ok(())
+}
+
+fn print_benchmark(benchmark: &Benchmark) {
+    let phases = [
+        ("parse", &benchmark.parse),
+        ("part 1", &benchmark.part1),
+        ("part 2", &benchmark.part2),
+    ];
+
+    for (phase, stats) in phases {
+        println!(
+            "day {:>2} {:<6}  min {:>12}  median {:>12}  p95 {:>12}",
+            benchmark.day,
+            phase,
+            format_nanos(stats.min_ns),
+            format_nanos(stats.median_ns),
+            format_nanos(stats.p95_ns),
+        );
+    }
+}
+
+fn format_nanos(nanos: u64) -> String {
+    format!("{:.2?}", Duration::from_nanos(nanos))
+}
+
+fn load_params() -> Result<params::Config> {
+    Ok(params::Config::load(Path::new(params::CONFIG_PATH))?)
+}
+
+fn read_input(path: &PathBuf) -> Result<String> {
+    if path.as_os_str() == "-" {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .context("Could not read input from stdin")?;
+        return Ok(input);
+    }
+
+    fs::read_to_string(path).with_context(|| format!("Could not read input {}", path.display()))
+}
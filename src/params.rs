@@ -0,0 +1,346 @@
+//! Puzzle parameters, declared by the solvers with their defaults.
+//!
+//! `aoc.toml` overrides them per day, and per input file of a day:
+//!
+//! ```toml
+//! [day15]
+//! interested_row = 2000000
+//!
+//! [day15."data/day15.test"]
+//! interested_row = 10
+//! ```
+//!
+//! `aoc run --set name=value` overrides both.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt, fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::Solver;
+
+pub const CONFIG_PATH: &str = "aoc.toml";
+
+/// A parameter of a solution, like the size of a rope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub default: i64,
+    /// smallest and largest values the solution can handle, both included
+    pub min: i64,
+    pub max: i64,
+    pub description: &'static str,
+}
+
+/// The values of the parameters of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Params {
+    day: u8,
+    values: BTreeMap<&'static str, i64>,
+    /// the smallest and largest value of every parameter
+    ranges: BTreeMap<&'static str, (i64, i64)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    /// the day does not declare the parameter, the declared ones are listed
+    Unknown {
+        day: u8,
+        name: String,
+        declared: Vec<&'static str>,
+    },
+    /// the value is outside of the range the solution can handle
+    OutOfRange {
+        day: u8,
+        name: String,
+        value: i64,
+        min: i64,
+        max: i64,
+    },
+    Config(String),
+}
+
+/// The overrides of `aoc.toml`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    days: BTreeMap<u8, BTreeMap<String, Override>>,
+}
+
+/// A value for the whole day, or the values for an input file.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+enum Override {
+    Value(i64),
+    Input(BTreeMap<String, i64>),
+}
+
+impl Params {
+    /// returns the defaults of the parameters
+    pub fn new(day: u8, parameters: &[Parameter]) -> Self {
+        Params {
+            day,
+            values: parameters
+                .iter()
+                .map(|parameter| (parameter.name, parameter.default))
+                .collect(),
+            ranges: parameters
+                .iter()
+                .map(|parameter| (parameter.name, (parameter.min, parameter.max)))
+                .collect(),
+        }
+    }
+
+    pub fn set(&mut self, name: &str, value: i64) -> Result<(), ParamError> {
+        match self.values.get_mut(name) {
+            Some(current) => {
+                let (min, max) = self.ranges[name];
+                if !(min..=max).contains(&value) {
+                    return Err(ParamError::OutOfRange {
+                        day: self.day,
+                        name: name.to_string(),
+                        value,
+                        min,
+                        max,
+                    });
+                }
+                *current = value;
+                Ok(())
+            }
+            None => Err(ParamError::Unknown {
+                day: self.day,
+                name: name.to_string(),
+                declared: self.values.keys().copied().collect(),
+            }),
+        }
+    }
+
+    /// returns the value of a declared parameter, panics if the solution did not declare it
+    /// or the value does not fit in `T`
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> T {
+        let value = *self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("Day {} does not declare parameter {}", self.day, name));
+
+        T::try_from(value).unwrap_or_else(|_| {
+            panic!(
+                "Parameter {} of day {} is out of range: {}",
+                name, self.day, value
+            )
+        })
+    }
+
+    /// returns the parameters with their values, sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, i64)> + '_ {
+        self.values.iter().map(|(name, value)| (*name, *value))
+    }
+}
+
+impl Config {
+    /// reads the overrides, none if the file does not exist
+    pub fn load(path: &Path) -> Result<Self, ParamError> {
+        match fs::read_to_string(path) {
+            Ok(config) => Config::parse(&config),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(error) => Err(ParamError::Config(error.to_string())),
+        }
+    }
+
+    pub fn parse(config: &str) -> Result<Self, ParamError> {
+        let tables: BTreeMap<String, BTreeMap<String, Override>> =
+            toml::from_str(config).map_err(|error| ParamError::Config(error.to_string()))?;
+
+        let mut days = BTreeMap::new();
+        for (key, overrides) in tables {
+            let day = key
+                .strip_prefix("day")
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or_else(|| {
+                    ParamError::Config(format!("`{}` should be a day like `day15`", key))
+                })?;
+            days.insert(day, overrides);
+        }
+
+        Ok(Config { days })
+    }
+
+    /// returns the parameters of the solver for the input file, with the overrides of the day
+    /// and then those of the input applied
+    pub fn params(&self, solver: &dyn Solver, input: &Path) -> Result<Params, ParamError> {
+        let mut params = Params::new(solver.day(), solver.parameters());
+        let Some(overrides) = self.days.get(&solver.day()) else {
+            return Ok(params);
+        };
+
+        for (name, value) in overrides {
+            if let Override::Value(value) = value {
+                params.set(name, *value)?;
+            }
+        }
+        for (path, value) in overrides {
+            match value {
+                Override::Input(values) if same_file(Path::new(path), input) => {
+                    for (name, value) in values {
+                        params.set(name, *value)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(params)
+    }
+}
+
+/// returns true if the paths name the same file, like `./data/day15.test` and `data/day15.test`,
+/// paths of missing files are only compared without their `.` components
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => {
+            let lexical = |path: &Path| {
+                path.components()
+                    .filter(|component| *component != Component::CurDir)
+                    .collect::<PathBuf>()
+            };
+            lexical(a) == lexical(b)
+        }
+    }
+}
+
+/// parses a `name=value` assignment of `--set`
+pub fn parse_assignment(assignment: &str) -> Result<(String, i64), String> {
+    let (name, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| format!("`{}` should look like name=value", assignment))?;
+    let value = value
+        .trim()
+        .replace('_', "")
+        .parse()
+        .map_err(|_| format!("`{}` is not an integer", value))?;
+
+    Ok((name.trim().to_string(), value))
+}
+
+impl fmt::Display for ParamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamError::Unknown {
+                day,
+                name,
+                declared,
+            } if declared.is_empty() => {
+                write!(f, "day {} has no parameter {}, it has none", day, name)
+            }
+            ParamError::Unknown {
+                day,
+                name,
+                declared,
+            } => write!(
+                f,
+                "day {} has no parameter {}, it has {}",
+                day,
+                name,
+                declared.join(", ")
+            ),
+            ParamError::OutOfRange {
+                day,
+                name,
+                value,
+                min,
+                max,
+            } => {
+                write!(
+                    f,
+                    "parameter {} of day {} is {}, it should be ",
+                    name, day, value
+                )?;
+                match (*min, *max) {
+                    (min, i64::MAX) => write!(f, "at least {}", min),
+                    (min, max) => write!(f, "between {} and {}", min, max),
+                }
+            }
+            ParamError::Config(error) => write!(f, "invalid {}: {}", CONFIG_PATH, error),
+        }
+    }
+}
+
+impl Error for ParamError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SolutionSolver;
+
+    const CONFIG: &str = r#"
+[day15]
+interested_row = 2_000_000
+
+[day15."data/day15.test"]
+interested_row = 10
+max_range = 20
+"#;
+
+    #[test]
+    fn input_overrides_the_day() {
+        let config = Config::parse(CONFIG).unwrap();
+        let solver = SolutionSolver::<crate::day15::Day15>::new();
+
+        let params = config
+            .params(&solver, Path::new("data/day15.test"))
+            .unwrap();
+        assert_eq!(params.get::<isize>("interested_row"), 10);
+        assert_eq!(params.get::<isize>("max_range"), 20);
+
+        for path in ["./data/day15.test", "data/./day15.test"] {
+            let params = config.params(&solver, Path::new(path)).unwrap();
+            assert_eq!(params.get::<isize>("interested_row"), 10, "{}", path);
+        }
+
+        let params = config
+            .params(&solver, Path::new("data/day15.input"))
+            .unwrap();
+        assert_eq!(params.get::<isize>("max_range"), 4_000_000);
+    }
+
+    #[test]
+    fn rejects_unknown_parameters() {
+        let mut params = Params::new(
+            9,
+            &[Parameter {
+                name: "rope_size",
+                default: 2,
+                min: 1,
+                max: 1000,
+                description: "knots of the rope",
+            }],
+        );
+
+        params.set("rope_size", 10).unwrap();
+        assert_eq!(params.get::<usize>("rope_size"), 10);
+        assert_eq!(
+            params.set("rope_size", 0).unwrap_err().to_string(),
+            "parameter rope_size of day 9 is 0, it should be between 1 and 1000"
+        );
+        assert_eq!(params.get::<usize>("rope_size"), 10);
+        assert!(matches!(
+            params.set("knots", 10),
+            Err(ParamError::Unknown { day: 9, .. })
+        ));
+        assert!(Config::parse("[fifteen]\nmax_range = 20").is_err());
+    }
+
+    #[test]
+    fn parses_assignments() {
+        assert_eq!(
+            parse_assignment("max_range=4_000_000"),
+            Ok(("max_range".to_string(), 4_000_000))
+        );
+        assert!(parse_assignment("max_range").is_err());
+        assert!(parse_assignment("max_range=far").is_err());
+    }
+}
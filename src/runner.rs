@@ -0,0 +1,227 @@
+//! Solving many days at once on a pool of threads.
+
+use std::{
+    any::Any,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{params::Params, ParseError, Solver};
+
+/// A part of a day to solve.
+pub struct Job {
+    pub solver: &'static dyn Solver,
+    pub part: u8,
+    pub input: Arc<str>,
+    pub params: Params,
+}
+
+/// The reason a job did not produce an answer.
+#[derive(Debug, Clone)]
+pub enum Failure {
+    Input(String),
+    Parse(ParseError),
+    Panic(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Result<String, Failure>,
+    /// time spent parsing and solving, or looking up a cached answer
+    pub elapsed: Duration,
+    pub cached: bool,
+}
+
+/// The machine readable form of an outcome, as printed by `--format json`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    /// `None` when the job failed
+    pub answer: Option<String>,
+    pub elapsed_ms: f64,
+    pub cached: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<&Outcome> for Report {
+    fn from(outcome: &Outcome) -> Self {
+        Report {
+            day: outcome.day,
+            part: outcome.part,
+            answer: outcome.answer.as_ref().ok().cloned(),
+            elapsed_ms: outcome.elapsed.as_secs_f64() * 1000.0,
+            cached: outcome.cached,
+            error: outcome
+                .answer
+                .as_ref()
+                .err()
+                .map(|failure| failure.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Input(error) => write!(f, "no input: {}", error),
+            Failure::Parse(error) => write!(f, "{}", error),
+            Failure::Panic(message) => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+impl From<ParseError> for Failure {
+    fn from(error: ParseError) -> Self {
+        Failure::Parse(error)
+    }
+}
+
+/// returns the default input file of a day
+pub fn default_input(day: u8) -> PathBuf {
+    PathBuf::from(format!("data/day{}.input", day))
+}
+
+/// parses and solves every job on `threads` threads, returning the outcomes in the order of the jobs,
+/// a panicking solver only fails its own job
+pub fn run_all(jobs: Vec<Job>, threads: usize) -> Vec<Outcome> {
+    let job_count = jobs.len();
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, job_count.max(1)) {
+            let sender = sender.clone();
+            let queue = &queue;
+
+            scope.spawn(move || loop {
+                // release the lock before solving
+                let next = queue.lock().unwrap().next();
+                let Some((index, job)) = next else {
+                    break;
+                };
+
+                sender
+                    .send((index, run_job(&job)))
+                    .expect("The outcomes should be received till every job is done");
+            });
+        }
+    });
+    drop(sender);
+
+    let mut outcomes = receiver.into_iter().collect::<Vec<(usize, Outcome)>>();
+    outcomes.sort_by_key(|(index, _)| *index);
+
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+fn run_job(job: &Job) -> Outcome {
+    let now = Instant::now();
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| -> Result<String, Failure> {
+        let parsed = job.solver.parse(&job.input)?;
+        Ok(job.solver.solve(parsed.as_ref(), job.part, &job.params))
+    }))
+    .unwrap_or_else(|payload| Err(Failure::Panic(panic_message(payload))));
+
+    Outcome {
+        day: job.solver.day(),
+        part: job.part,
+        answer,
+        elapsed: now.elapsed(),
+        cached: false,
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// answers the input, or panics on part 2
+    struct Echo;
+
+    impl Solver for Echo {
+        fn day(&self) -> u8 {
+            99
+        }
+
+        fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+            match input {
+                "" => Err(ParseError::new(99, input, "some input")),
+                _ => Ok(Box::new(input.to_string())),
+            }
+        }
+
+        fn solve(&self, input: &dyn Any, part: u8, _params: &Params) -> String {
+            match part {
+                1 => input.downcast_ref::<String>().unwrap().clone(),
+                _ => panic!("part {} is broken", part),
+            }
+        }
+    }
+
+    #[test]
+    fn panics_only_fail_their_own_job() {
+        let jobs = [(1, "a"), (2, "b"), (1, ""), (1, "c")]
+            .into_iter()
+            .map(|(part, input)| Job {
+                solver: &Echo,
+                part,
+                input: input.into(),
+                params: Params::new(99, &[]),
+            })
+            .collect();
+
+        let outcomes = run_all(jobs, 3);
+
+        assert_eq!(outcomes.len(), 4);
+        assert_eq!(outcomes[0].answer.as_ref().unwrap(), "a");
+        assert!(
+            matches!(&outcomes[1].answer, Err(Failure::Panic(message)) if message == "part 2 is broken")
+        );
+        assert!(matches!(outcomes[2].answer, Err(Failure::Parse(_))));
+        assert_eq!(outcomes[3].answer.as_ref().unwrap(), "c");
+    }
+
+    #[test]
+    fn reports_serialize_answers_or_errors() {
+        let outcome = Outcome {
+            day: 3,
+            part: 1,
+            answer: Ok("157".to_string()),
+            elapsed: Duration::from_micros(1500),
+            cached: false,
+        };
+        assert_eq!(
+            serde_json::to_string(&Report::from(&outcome)).unwrap(),
+            r#"{"day":3,"part":1,"answer":"157","elapsed_ms":1.5,"cached":false}"#
+        );
+
+        let outcome = Outcome {
+            answer: Err(Failure::Panic("oops".to_string())),
+            ..outcome
+        };
+        assert_eq!(
+            serde_json::to_string(&Report::from(&outcome)).unwrap(),
+            r#"{"day":3,"part":1,"answer":null,"elapsed_ms":1.5,"cached":false,"error":"panicked: oops"}"#
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Scaffolding of the files of a new day.
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+/// solver module of a new day, `{day}` is replaced by the day
+const TEMPLATE: &str = r#"use crate::{error, params::Params, ParseError, Solution};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    const DAY: u8 = {day};
+
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(error::lines(Self::DAY, input)
+            .map(|line| line.text.to_string())
+            .collect())
+    }
+
+    fn part1(_input: &Self::Input, _params: &Params) -> Self::Answer1 {
+        todo!("part 1 of day {day}")
+    }
+
+    fn part2(_input: &Self::Input, _params: &Params) -> Self::Answer2 {
+        todo!("part 2 of day {day}")
+    }
+}
+"#;
+
+/// creates the solver module, registers it in `src/lib.rs`, creates the empty test and input files
+/// and adds ignored entries for the test file to `answers.toml`, returns the created or changed files
+pub fn new_day(root: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+    let module = root.join(format!("src/day{}.rs", day));
+    if module.exists() {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+
+    let lib = root.join("src/lib.rs");
+    let registered = register(&fs::read_to_string(&lib)?, day).ok_or_else(|| {
+        io::Error::new(
+            ErrorKind::InvalidData,
+            format!("Could not register day {} in {}", day, lib.display()),
+        )
+    })?;
+
+    fs::write(&module, TEMPLATE.replace("{day}", &day.to_string()))?;
+    fs::write(&lib, registered)?;
+    let mut changed = vec![module, lib];
+
+    // an input could already be downloaded
+    for extension in ["test", "input"] {
+        let data = root.join(format!("data/day{}.{}", day, extension));
+        if !data.exists() {
+            fs::write(&data, "")?;
+            changed.push(data);
+        }
+    }
+
+    let manifest = root.join("answers.toml");
+    let mut answers = fs::read_to_string(&manifest)?;
+    answers.push_str(&answer_stubs(day));
+    fs::write(&manifest, answers)?;
+    changed.push(manifest);
+
+    Ok(changed)
+}
+
+/// adds the module of the day and its solver to the source of `src/lib.rs`,
+/// `None` if it is already registered or the list of solvers is not found
+pub fn register(lib: &str, day: u8) -> Option<String> {
+    let module = format!("day{}", day);
+    let module_line = format!("pub mod {};", module);
+    let solver_line = format!("    &SolutionSolver::<{}::Day{}>::new(),", module, day);
+
+    let mut lines = lib.lines().map(str::to_string).collect::<Vec<String>>();
+    if lines.contains(&module_line) {
+        return None;
+    }
+
+    // modules are sorted by name
+    let module_name = |line: &str| {
+        line.strip_prefix("pub mod ")?
+            .strip_suffix(';')
+            .map(str::to_string)
+    };
+    let module_index = lines
+        .iter()
+        .position(|line| module_name(line).is_some_and(|name| name > module))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| module_name(line).is_some())
+                .map(|index| index + 1)
+        })?;
+    lines.insert(module_index, module_line);
+
+    // solvers are sorted by day
+    let solvers_start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static SOLVERS"))?;
+    let solvers_end = solvers_start
+        + lines[solvers_start..]
+            .iter()
+            .position(|line| line == "];")?;
+    let solver_index = (solvers_start + 1..solvers_end)
+        .find(|index| solver_day(&lines[*index]).is_some_and(|other| other > day))
+        .unwrap_or(solvers_end);
+    lines.insert(solver_index, solver_line);
+
+    Some(lines.join("\n") + "\n")
+}
+
+/// returns the day of a line like `&SolutionSolver::<day3::Day3>::new(),`
+fn solver_day(line: &str) -> Option<u8> {
+    let (_, rest) = line.split_once("::<day")?;
+    let (day, _) = rest.split_once("::")?;
+    day.parse().ok()
+}
+
+/// returns ignored entries of both parts of the test file, to fill in once the answers are known
+pub fn answer_stubs(day: u8) -> String {
+    (1..=2)
+        .map(|part| {
+            format!(
+                "\n[[answer]]\nday = {}\npart = {}\ninput = \"data/day{}.test\"\nanswer = \"\"\nignore = \"the answer is not known yet\"\n",
+                day, part, day
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::Manifest;
+
+    const LIB: &str = "pub mod bench;
+pub mod day1;
+pub mod day2;
+pub mod error;
+
+/// every solved day, in order
+pub static SOLVERS: &[&dyn Solver] = &[
+    &SolutionSolver::<day1::Day1>::new(),
+    &SolutionSolver::<day2::Day2>::new(),
+];
+";
+
+    #[test]
+    fn registers_module_and_solver_in_order() {
+        let lib = register(LIB, 19).unwrap();
+
+        assert!(lib.contains("pub mod day1;\npub mod day19;\npub mod day2;\n"));
+        assert!(
+            lib.contains("<day2::Day2>::new(),\n    &SolutionSolver::<day19::Day19>::new(),\n];")
+        );
+        assert_eq!(register(&lib, 19), None);
+    }
+
+    #[test]
+    fn answer_stubs_are_ignored_entries() {
+        let manifest = Manifest::parse(&answer_stubs(19)).unwrap();
+
+        assert_eq!(manifest.answer.len(), 2);
+        assert!(manifest
+            .answer
+            .iter()
+            .all(|answer| answer.day == 19 && answer.ignore.is_some()));
+    }
+
+    #[test]
+    fn creates_every_file() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("data")).unwrap();
+        fs::write(root.join("src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("answers.toml"), "").unwrap();
+        fs::write(root.join("data/day19.input"), "downloaded").unwrap();
+
+        let changed = new_day(&root, 19).unwrap();
+
+        assert_eq!(changed.len(), 4);
+        assert!(fs::read_to_string(root.join("src/day19.rs"))
+            .unwrap()
+            .contains("impl Solution for Day19 {\n    const DAY: u8 = 19;"));
+        assert!(fs::read_to_string(root.join("src/day19.rs"))
+            .unwrap()
+            .contains("error::lines(Self::DAY, input)"));
+        assert_eq!(
+            fs::read_to_string(root.join("data/day19.test")).unwrap(),
+            ""
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("data/day19.input")).unwrap(),
+            "downloaded"
+        );
+        assert!(new_day(&root, 19).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
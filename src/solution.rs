@@ -0,0 +1,109 @@
+use std::{
+    any::Any,
+    fmt::{self, Display},
+    marker::PhantomData,
+};
+
+use tracing::info_span;
+
+use crate::{
+    params::{Parameter, Params},
+    ParseError,
+};
+
+/// A puzzle solution, parsing the puzzle input once into a typed model which both parts solve.
+pub trait Solution {
+    /// Day of the puzzle
+    const DAY: u8;
+
+    /// Model of the puzzle input
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// Tunable parameters of the puzzle with their defaults
+    const PARAMETERS: &'static [Parameter] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input, params: &Params) -> Self::Answer1;
+
+    fn part2(input: &Self::Input, params: &Params) -> Self::Answer2;
+}
+
+/// An answer which not every puzzle input has, shown as why it is missing otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Maybe<T> {
+    Found(T),
+    Missing(&'static str),
+}
+
+impl<T: Display> Display for Maybe<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Maybe::Found(answer) => answer.fmt(f),
+            Maybe::Missing(reason) => f.write_str(reason),
+        }
+    }
+}
+
+/// Type erased [`Solution`], allowing the runner to dispatch on the day.
+pub trait Solver: Sync {
+    fn day(&self) -> u8;
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[]
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+
+    /// solves `part` (1 or 2) of the parsed input
+    fn solve(&self, input: &dyn Any, part: u8, params: &Params) -> String;
+}
+
+/// Adapts a [`Solution`] into a [`Solver`].
+pub struct SolutionSolver<S>(PhantomData<fn() -> S>);
+
+impl<S> SolutionSolver<S> {
+    pub const fn new() -> Self {
+        SolutionSolver(PhantomData)
+    }
+}
+
+impl<S> Default for SolutionSolver<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Solver for SolutionSolver<S>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        S::PARAMETERS
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        let _span = info_span!("parse", day = S::DAY).entered();
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, input: &dyn Any, part: u8, params: &Params) -> String {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("Input was not parsed by the same solution");
+        let _span = info_span!("solve", day = S::DAY, part).entered();
+
+        match part {
+            1 => S::part1(input, params).to_string(),
+            2 => S::part2(input, params).to_string(),
+            _ => panic!("A puzzle only has 2 parts"),
+        }
+    }
+}
@@ -0,0 +1,365 @@
+//! Animations of the simulations of the puzzles in the terminal.
+//!
+//! A simulation advances one step at a time and renders its state as text, the [`Player`]
+//! draws those frames with ANSI escapes and reacts to the keys:
+//!
+//! - `space` plays or pauses
+//! - `n` or `→` steps once while paused
+//! - `+` and `-` double or halve the speed
+//! - `q` quits
+
+use std::{
+    io::{self, IsTerminal, Read, Write},
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use crate::{day10, day14, day17, day9, params::Params, ParseError, Solution};
+
+/// A snapshot of the state of a simulation which can be drawn as text.
+pub trait Render {
+    /// returns the lines of the drawing
+    fn render(&self) -> String;
+}
+
+/// A simulation which is drawn after every step.
+pub trait Simulation: Render {
+    /// advances the simulation by one step, returns false once it has ended
+    fn step(&mut self) -> bool;
+}
+
+/// the most frames drawn per second, faster speeds take several steps per frame
+const FRAME_RATE: f64 = 60.0;
+
+/// the most steps taken without a terminal, some simulations like the trillion blocks
+/// of day 17 never end in time
+const HEADLESS_STEPS: usize = 1_000_000;
+
+/// returns the simulation of a part of the day, `None` if the day has no visualization
+pub fn simulation(
+    day: u8,
+    part: u8,
+    input: &str,
+    params: &Params,
+) -> Result<Option<Box<dyn Simulation>>, ParseError> {
+    let simulation: Box<dyn Simulation> = match day {
+        9 => {
+            let rope_size = match part {
+                1 => params.get("short_rope_size"),
+                _ => params.get("long_rope_size"),
+            };
+            Box::new(day9::Rope::new(rope_size, &day9::Day9::parse(input)?))
+        }
+        10 => Box::new(day10::Crt::new(&day10::Day10::parse(input)?)),
+        14 => Box::new(day14::Sand::new(&day14::Day14::parse(input)?, part == 2)),
+        17 => {
+            let blocks = match part {
+                1 => params.get("short_tower_blocks"),
+                _ => params.get("tall_tower_blocks"),
+            };
+            Box::new(day17::Tower::new(&day17::Day17::parse(input)?, blocks))
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(simulation))
+}
+
+/// A key which controls the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    PlayPause,
+    Step,
+    Faster,
+    Slower,
+    Quit,
+}
+
+/// Plays a simulation at a speed in steps per second.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Player {
+    speed: f64,
+    playing: bool,
+    finished: bool,
+    steps: usize,
+}
+
+impl Player {
+    pub fn new(speed: f64) -> Self {
+        Player {
+            speed: speed.clamp(0.5, 1_000_000.0),
+            playing: true,
+            finished: false,
+            steps: 0,
+        }
+    }
+
+    /// reacts to a key, returns false when the player should quit
+    pub fn press(&mut self, key: Key, simulation: &mut dyn Simulation) -> bool {
+        match key {
+            Key::PlayPause => self.playing = !self.playing && !self.finished,
+            Key::Step => {
+                self.playing = false;
+                self.advance(simulation, 1);
+            }
+            Key::Faster => self.speed = (self.speed * 2.0).min(1_000_000.0),
+            Key::Slower => self.speed = (self.speed / 2.0).max(0.5),
+            Key::Quit => return false,
+        }
+
+        true
+    }
+
+    /// takes the steps of a frame, the player pauses once the simulation has ended
+    pub fn advance(&mut self, simulation: &mut dyn Simulation, steps: usize) {
+        for _ in 0..steps {
+            if self.finished || !simulation.step() {
+                self.finished = true;
+                self.playing = false;
+                return;
+            }
+            self.steps += 1;
+        }
+    }
+
+    /// returns the steps taken per frame and the time between frames
+    pub fn pace(&self) -> (usize, Duration) {
+        let steps = (self.speed / FRAME_RATE).ceil().max(1.0);
+
+        (steps as usize, Duration::from_secs_f64(steps / self.speed))
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// returns the line below the frame
+    pub fn status(&self) -> String {
+        let state = match (self.finished, self.playing) {
+            (true, _) => "finished",
+            (false, true) => "playing",
+            (false, false) => "paused",
+        };
+
+        format!(
+            "{} | step {} | {} steps/s | space: play/pause  n: step  +/-: speed  q: quit",
+            state, self.steps, self.speed
+        )
+    }
+}
+
+impl Key {
+    /// returns the key of a byte read from the terminal, arrows are read as `[C` after escape
+    fn from_byte(byte: u8) -> Option<Key> {
+        match byte {
+            b' ' | b'p' => Some(Key::PlayPause),
+            b'n' | b'l' | b'C' => Some(Key::Step),
+            b'+' | b'=' => Some(Key::Faster),
+            b'-' | b'_' => Some(Key::Slower),
+            // ctrl-c is a byte as well, the terminal does not send signals while playing
+            b'q' | 3 => Some(Key::Quit),
+            _ => None,
+        }
+    }
+}
+
+/// plays the simulation in the terminal till it is quit, without a terminal
+/// the simulation runs till its end, or for at most a million steps, and only its last frame
+/// is printed
+pub fn play(simulation: &mut dyn Simulation, speed: f64) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    if !stdout.is_terminal() || !io::stdin().is_terminal() {
+        if !run(simulation, HEADLESS_STEPS) {
+            writeln!(
+                stdout,
+                "stopped after {} steps, before the end of the simulation",
+                HEADLESS_STEPS
+            )?;
+        }
+        return writeln!(stdout, "{}", simulation.render());
+    }
+
+    let _terminal = Terminal::enter()?;
+    let keys = read_keys();
+    let mut player = Player::new(speed);
+
+    loop {
+        write!(stdout, "\x1b[H")?;
+        for line in simulation.render().lines() {
+            write!(stdout, "{}\x1b[K\r\n", line)?;
+        }
+        write!(stdout, "{}\x1b[K\x1b[J", player.status())?;
+        stdout.flush()?;
+
+        let (steps, interval) = player.pace();
+        let key = match player.is_playing() {
+            true => keys.recv_timeout(interval).ok(),
+            false => keys.recv().ok(),
+        };
+
+        match key {
+            Some(key) => {
+                if !player.press(key, simulation) {
+                    return Ok(());
+                }
+            }
+            None if player.is_playing() => player.advance(simulation, steps),
+            // stdin was closed
+            None => return Ok(()),
+        }
+    }
+}
+
+/// takes at most `steps` steps, returns true if the simulation has ended within them
+fn run(simulation: &mut dyn Simulation, steps: usize) -> bool {
+    (0..steps).any(|_| !simulation.step())
+}
+
+/// returns the keys pressed in the terminal
+fn read_keys() -> mpsc::Receiver<Key> {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for byte in io::stdin().lock().bytes() {
+            let Ok(byte) = byte else {
+                return;
+            };
+            if let Some(key) = Key::from_byte(byte) {
+                if sender.send(key).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+
+    receiver
+}
+
+/// The terminal in raw mode on the alternate screen, restored when dropped.
+struct Terminal {
+    settings: String,
+}
+
+impl Terminal {
+    fn enter() -> io::Result<Self> {
+        let settings = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "1"])?;
+        print!("\x1b[?1049h\x1b[?25l");
+
+        Ok(Terminal {
+            settings: settings.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.settings]);
+    }
+}
+
+/// changes the settings of the terminal of stdin, returns what `stty` printed
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(String::from_utf8_lossy(&output.stderr)));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// counts to a limit
+    struct Counter(usize, usize);
+
+    impl Render for Counter {
+        fn render(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            self.0 += 1;
+            self.0 <= self.1
+        }
+    }
+
+    #[test]
+    fn steps_pauses_and_finishes() {
+        let mut counter = Counter(0, 3);
+        let mut player = Player::new(10.0);
+
+        assert!(player.press(Key::Step, &mut counter));
+        assert!(!player.is_playing());
+        assert_eq!(counter.render(), "1");
+
+        player.press(Key::PlayPause, &mut counter);
+        player.advance(&mut counter, 5);
+        assert!(!player.is_playing());
+        assert!(player.status().starts_with("finished | step 3 |"));
+
+        // a finished simulation does not play again
+        player.press(Key::PlayPause, &mut counter);
+        assert!(!player.is_playing());
+        assert!(!player.press(Key::Quit, &mut counter));
+    }
+
+    #[test]
+    fn runs_a_limited_amount_of_steps() {
+        assert!(run(&mut Counter(0, 3), 10));
+        // the fourth step tells it has ended
+        assert!(run(&mut Counter(0, 3), 4));
+        assert!(!run(&mut Counter(0, 3), 3));
+
+        let mut counter = Counter(0, 100);
+        assert!(!run(&mut counter, 10));
+        assert_eq!(counter.render(), "10");
+    }
+
+    #[test]
+    fn takes_more_steps_per_frame_when_faster() {
+        let mut counter = Counter(0, 0);
+        let mut player = Player::new(30.0);
+        assert_eq!(player.pace(), (1, Duration::from_secs_f64(1.0 / 30.0)));
+
+        for _ in 0..3 {
+            player.press(Key::Faster, &mut counter);
+        }
+        assert_eq!(player.pace(), (4, Duration::from_secs_f64(4.0 / 240.0)));
+    }
+
+    #[test]
+    fn animates_the_examples() {
+        let examples = [
+            (9, include_str!("../data/day9.test")),
+            (10, include_str!("../data/day10.test")),
+            (14, include_str!("../data/day14.test")),
+            (17, include_str!("../data/day17.test")),
+        ];
+
+        for (day, input) in examples {
+            let solver = crate::solver(day).unwrap();
+            let params = Params::new(day, solver.parameters());
+            let mut simulation = simulation(day, 1, input, &params).unwrap().unwrap();
+
+            let first = simulation.render();
+            assert!(simulation.step());
+            assert!(!simulation.render().is_empty());
+            assert_ne!(simulation.render(), first, "day {}", day);
+        }
+        assert!(simulation(1, 1, "1\n", &Params::new(1, &[]))
+            .unwrap()
+            .is_none());
+    }
+}
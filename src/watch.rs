@@ -0,0 +1,290 @@
+//! Rerunning the solver of a day whenever one of its input files changes.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{answers::Manifest, runner::Outcome};
+
+/// Highlighting of a line of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Pass,
+    Fail,
+    Dim,
+}
+
+type Cell = Vec<(String, Style)>;
+
+/// returns if the file name belongs to the day, like `day9.test` or `day9part2.test` for day 9
+pub fn is_input_of(day: u8, name: &str) -> bool {
+    name.strip_prefix(&format!("day{}", day))
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|next| !next.is_ascii_digit())
+}
+
+/// returns the test files of the day in `dir`, like `day6.test` and `day6.test2`,
+/// and its `.input` files with `with_input`, sorted by name
+pub fn inputs(dir: &Path, day: u8, with_input: bool) -> io::Result<Vec<PathBuf>> {
+    let mut inputs = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let (Some(name), Some(extension)) = (
+            path.file_name().and_then(|name| name.to_str()),
+            path.extension().and_then(|extension| extension.to_str()),
+        ) else {
+            continue;
+        };
+
+        if is_input_of(day, name)
+            && (extension.starts_with("test") || with_input && extension == "input")
+        {
+            inputs.push(path);
+        }
+    }
+    inputs.sort();
+
+    Ok(inputs)
+}
+
+/// returns a table with a column of answers per input file, answers which differ from the
+/// expected answer are highlighted line by line, followed by the expected answer
+pub fn render(
+    day: u8,
+    results: &[(PathBuf, Vec<Outcome>)],
+    manifest: &Manifest,
+    color: bool,
+) -> String {
+    let mut columns = vec![vec![
+        vec![(String::new(), Style::Plain)],
+        vec![("part 1".to_string(), Style::Plain)],
+        vec![("part 2".to_string(), Style::Plain)],
+    ]];
+
+    for (input, outcomes) in results {
+        let name = input.file_name().unwrap_or_default().to_string_lossy();
+        let mut column = vec![vec![(name.to_string(), Style::Plain)]];
+
+        for part in 1..=2 {
+            let cell = match outcomes.iter().find(|outcome| outcome.part == part) {
+                Some(outcome) => {
+                    // the stubs of `aoc new` have no answer yet
+                    let expected = manifest
+                        .expected(day, part, input)
+                        .map(|expected| expected.answer.as_str())
+                        .filter(|expected| !expected.is_empty());
+                    outcome_cell(outcome, expected)
+                }
+                None => vec![],
+            };
+            column.push(cell);
+        }
+        columns.push(column);
+    }
+
+    let widths = columns
+        .iter()
+        .map(|column| {
+            column
+                .iter()
+                .flatten()
+                .map(|(line, _)| line.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<usize>>();
+
+    let mut table = String::new();
+    for row in 0..3 {
+        let height = columns
+            .iter()
+            .map(|column| column[row].len())
+            .max()
+            .unwrap_or_default();
+
+        for line in 0..height {
+            let cells = columns.iter().zip(&widths).map(|(column, width)| {
+                let (text, style) = column[row]
+                    .get(line)
+                    .cloned()
+                    .unwrap_or((String::new(), Style::Plain));
+                paint(&format!("{:<width$}", text, width = width), style, color)
+            });
+
+            table.push_str(cells.collect::<Vec<String>>().join("  ").trim_end());
+            table.push('\n');
+        }
+    }
+
+    table
+}
+
+fn outcome_cell(outcome: &Outcome, expected: Option<&str>) -> Cell {
+    let answer = match &outcome.answer {
+        Ok(answer) => answer,
+        Err(failure) => return vec![(failure.to_string(), Style::Fail)],
+    };
+    let Some(expected) = expected else {
+        return answer
+            .lines()
+            .map(|line| (line.to_string(), Style::Plain))
+            .collect();
+    };
+
+    let expected_lines = expected.lines().collect::<Vec<&str>>();
+    let mut cell = answer
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let style = match expected_lines.get(index) == Some(&line) {
+                true => Style::Pass,
+                false => Style::Fail,
+            };
+            (line.to_string(), style)
+        })
+        .collect::<Cell>();
+
+    if answer != expected {
+        cell.push(("expected".to_string(), Style::Dim));
+        cell.extend(
+            expected_lines
+                .iter()
+                .map(|line| (line.to_string(), Style::Dim)),
+        );
+    }
+
+    cell
+}
+
+fn paint(text: &str, style: Style, color: bool) -> String {
+    let code = match style {
+        Style::Plain => return text.to_string(),
+        _ if !color => return text.to_string(),
+        Style::Pass => "32",
+        Style::Fail => "31",
+        Style::Dim => "2",
+    };
+
+    format!("\x1b[{}m{}\x1b[0m", code, text)
+}
+
+/// Waits for changes to the input files of a day with inotify.
+#[cfg(target_os = "linux")]
+pub struct Watcher {
+    inotify: inotify::Inotify,
+    day: u8,
+    buffer: Vec<u8>,
+}
+
+#[cfg(target_os = "linux")]
+impl Watcher {
+    /// watches the files of the day in `dir`, including the ones created later
+    pub fn new(dir: &Path, day: u8) -> io::Result<Self> {
+        use inotify::{Inotify, WatchMask};
+
+        let inotify = Inotify::init()?;
+        // editors often write a new file and move it in place
+        inotify.watches().add(
+            dir,
+            WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::DELETE,
+        )?;
+
+        Ok(Watcher {
+            inotify,
+            day,
+            buffer: vec![0; 4096],
+        })
+    }
+
+    /// blocks till a file of the day changed, a burst of changes is reported once
+    pub fn wait(&mut self) -> io::Result<()> {
+        loop {
+            let events = self.inotify.read_events_blocking(&mut self.buffer)?;
+            let changed = events
+                .filter_map(|event| event.name?.to_str())
+                .any(|name| is_input_of(self.day, name));
+
+            if changed {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                // drop the rest of the burst
+                while self
+                    .inotify
+                    .read_events(&mut self.buffer)
+                    .is_ok_and(|mut events| events.next().is_some())
+                {}
+
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::runner::Failure;
+
+    #[test]
+    fn matches_the_files_of_the_day() {
+        assert!(is_input_of(9, "day9.test"));
+        assert!(is_input_of(9, "day9part2.test"));
+        assert!(is_input_of(1, "day1.input"));
+        assert!(!is_input_of(1, "day10.input"));
+        assert!(!is_input_of(1, "day1"));
+    }
+
+    #[test]
+    fn renders_inputs_side_by_side() {
+        let manifest = Manifest::parse(
+            r#"
+[[answer]]
+day = 9
+part = 1
+input = "data/day9.test"
+answer = "13"
+
+[[answer]]
+day = 9
+part = 2
+input = "data/day9.test"
+answer = "1"
+"#,
+        )
+        .unwrap();
+        let outcome = |part, answer: Result<&str, Failure>| Outcome {
+            day: 9,
+            part,
+            answer: answer.map(str::to_string),
+            elapsed: Duration::ZERO,
+            cached: false,
+        };
+        let results = [
+            (
+                PathBuf::from("data/day9.test"),
+                vec![outcome(1, Ok("13")), outcome(2, Ok("2"))],
+            ),
+            (
+                PathBuf::from("data/day9part2.test"),
+                vec![
+                    outcome(1, Ok("88")),
+                    outcome(2, Err(Failure::Panic("oops".to_string()))),
+                ],
+            ),
+        ];
+
+        assert_eq!(
+            render(9, &results, &manifest, false),
+            "        day9.test  day9part2.test
+part 1  13         88
+part 2  2          panicked: oops
+        expected
+        1
+"
+        );
+        assert!(render(9, &results, &manifest, true).contains(&format!("\x1b[31m{:<9}\x1b[0m", 2)));
+    }
+}
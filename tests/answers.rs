@@ -0,0 +1,22 @@
+use std::{fs, path::Path};
+
+use aoc::params::{Config, CONFIG_PATH};
+
+/// solves `part` of `day` for the input file and compares it with the expected answer
+fn check(day: u8, part: u8, input_path: &str, expected: &str) {
+    let solver = aoc::solver(day).unwrap_or_else(|| panic!("Day {} has no solver", day));
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let input = fs::read_to_string(root.join(input_path))
+        .unwrap_or_else(|_| panic!("Input {} not found!", input_path));
+
+    let params = Config::load(&root.join(CONFIG_PATH))
+        .and_then(|config| config.params(solver, Path::new(input_path)))
+        .unwrap_or_else(|error| panic!("{}", error));
+
+    let parsed = solver
+        .parse(&input)
+        .unwrap_or_else(|error| panic!("{}", error));
+    assert_eq!(solver.solve(parsed.as_ref(), part, &params), expected);
+}
+
+include!(concat!(env!("OUT_DIR"), "/answers.rs"));
@@ -0,0 +1,102 @@
+use aoc::{
+    day1::{self, Day1, Elf, TopK},
+    params::Params,
+    Solution,
+};
+
+fn totals(input: &str) -> Vec<u32> {
+    Day1::parse(input).unwrap().iter().map(Elf::total).collect()
+}
+
+#[test]
+fn keeps_the_last_elf_without_trailing_blank_line() {
+    assert_eq!(totals("1000\n2000\n\n3000"), [3000, 3000]);
+    assert_eq!(totals("1000\n2000\n\n3000\n\n"), [3000, 3000]);
+}
+
+#[test]
+fn accepts_crlf_and_whitespace_separators() {
+    assert_eq!(totals("1000\r\n2000\r\n\r\n3000\r\n"), [3000, 3000]);
+    assert_eq!(totals("1000\n  \n\t\n3000 \n"), [1000, 3000]);
+}
+
+#[test]
+fn rejects_input_without_elves() {
+    let error = Day1::parse("\n\n").unwrap_err();
+
+    assert_eq!(error.expected, "at least one elf carrying calories");
+    assert_eq!(Day1::parse("1000\nlots\n").unwrap_err().line, 2);
+}
+
+#[test]
+fn sums_the_top_elves() {
+    let elves = Day1::parse(include_str!("../data/day1.test")).unwrap();
+    let mut params = Params::new(1, Day1::PARAMETERS);
+
+    assert_eq!(Day1::part2(&elves, &params), 45000);
+    params.set("top_elves", 1).unwrap();
+    assert_eq!(Day1::part2(&elves, &params), Day1::part1(&elves, &params));
+}
+
+#[test]
+fn reports_ranked_elves() {
+    let elves = Day1::parse("1\n2\n3\n4\n\n10\n\n4\n6\n\n5").unwrap();
+    let report = day1::report(&elves, 3);
+
+    let ranked = report
+        .elves
+        .iter()
+        .map(|stats| (stats.rank, stats.elf, stats.total, stats.items))
+        .collect::<Vec<_>>();
+    assert_eq!(ranked, [(1, 1, 10, 4), (1, 2, 10, 1), (1, 3, 10, 2)]);
+    assert_eq!((report.elves[0].mean, report.elves[0].median), (2.5, 2.5));
+    assert_eq!(report.top_total, 30);
+
+    assert_eq!(
+        day1::report(&elves, 10).to_string().lines().last(),
+        Some("the top 4 elves carry 35 calories")
+    );
+    assert_eq!(day1::report(&elves, 10).elves[3].rank, 4);
+}
+
+#[test]
+fn streams_the_same_totals_as_the_parser() {
+    let input = include_str!("../data/day1.test");
+    let streamed = day1::totals(input.lines()).collect::<Result<Vec<u32>, _>>();
+
+    assert_eq!(streamed.unwrap(), totals(input));
+    assert_eq!(
+        day1::totals("1000\r\n\r\n  \n3000 ".split('\n'))
+            .collect::<Result<Vec<u32>, _>>()
+            .unwrap(),
+        [1000, 3000]
+    );
+}
+
+#[test]
+fn stops_streaming_at_the_first_error() {
+    let mut totals = day1::totals(["1000", "", "2000", "lots", "3000"]);
+
+    assert_eq!(totals.next().unwrap().unwrap(), 1000);
+    let error = totals.next().unwrap().unwrap_err();
+    assert_eq!((error.line, error.column), (4, 1));
+    assert!(totals.next().is_none());
+}
+
+#[test]
+fn keeps_the_largest_items_first() {
+    let items = [5, 1, 9, 3, 9, 7, 2];
+
+    assert_eq!(items.iter().copied().top_k(3), [9, 9, 7]);
+    assert_eq!(items.iter().copied().top_k(10).len(), items.len());
+    assert!(items.iter().copied().top_k(0).is_empty());
+    assert_eq!(items.iter().copied().top_k(usize::MAX).len(), items.len());
+}
+
+#[test]
+fn rejects_more_top_elves_than_realistic() {
+    let mut params = Params::new(1, Day1::PARAMETERS);
+
+    assert!(params.set("top_elves", i64::MAX).is_err());
+    assert!(params.set("top_elves", 1_000_000).is_ok());
+}
@@ -0,0 +1,45 @@
+use aoc::{day11::Day11, params::Params, Maybe, Solution};
+
+/// two monkeys squaring the worry level of the items they throw to each other
+fn squaring_monkeys(divisor: u64) -> String {
+    (0..2)
+        .map(|monkey| {
+            format!(
+                "Monkey {monkey}:
+  Starting items: 1000
+  Operation: new = old * old
+  Test: divisible by {divisor}
+    If true: throw to monkey {other}
+    If false: throw to monkey {other}
+",
+                other = 1 - monkey
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn worry_levels_beyond_64_bits_have_no_answer() {
+    let monkeys = Day11::parse(&squaring_monkeys(7)).unwrap();
+    let params = Params::new(11, Day11::PARAMETERS);
+
+    assert_eq!(
+        Day11::part1(&monkeys, &params),
+        Maybe::Missing("worry levels beyond 2^64")
+    );
+    // without relief the worry levels are kept modulo the divisors, the first monkey
+    // inspects both items from the second round on
+    assert_eq!(
+        Day11::part2(&monkeys, &params),
+        Maybe::Found(20_000 * 19_999)
+    );
+}
+
+#[test]
+fn rejects_divisors_without_a_common_multiple_below_64_bits() {
+    let error = Day11::parse(&squaring_monkeys(u32::MAX as u64 + 2)).unwrap_err();
+
+    assert_eq!((error.line, error.column), (11, 1));
+    assert_eq!(error.expected, "divisors with a common multiple below 2^64");
+}
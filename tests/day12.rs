@@ -0,0 +1,11 @@
+use aoc::{day12::Day12, params::Params, Maybe, Solution};
+
+#[test]
+fn walled_in_end_has_no_path() {
+    let map = Day12::parse("Sc\ncE\n").unwrap();
+    let params = Params::new(12, Day12::PARAMETERS);
+
+    assert_eq!(Day12::part1(&map, &params), Maybe::Missing("no path"));
+    assert_eq!(Day12::part2(&map, &params), Maybe::Missing("no path"));
+    assert_eq!(Day12::part2(&map, &params).to_string(), "no path");
+}
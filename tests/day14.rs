@@ -0,0 +1,19 @@
+use aoc::{day14::Day14, params::Params, Solution};
+
+#[test]
+fn sand_spreads_left_of_x_zero_on_a_deep_floor() {
+    let rocks = Day14::parse("498,600 -> 502,600\n").unwrap();
+    let params = Params::new(14, Day14::PARAMETERS);
+
+    assert_eq!(Day14::part1(&rocks, &params), 4);
+    // a triangle of 602 rows of sand, but for the rock and the sand it shelters
+    assert_eq!(Day14::part2(&rocks, &params), 362396);
+}
+
+#[test]
+fn deep_caves_do_not_allocate_the_whole_triangle() {
+    let rocks = Day14::parse("500,200000 -> 501,200000\n").unwrap();
+
+    // the ledge is too narrow to hold any sand, it all falls into the abyss
+    assert_eq!(Day14::part1(&rocks, &Params::new(14, Day14::PARAMETERS)), 0);
+}
@@ -0,0 +1,239 @@
+use aoc::{
+    day2::{
+        self, Day2, Decoding, Fixed, FollowTheGuide, FrequencyCounter, Game, Outcome, Player,
+        Random, Rules, RulesError, Shape, WinStayLoseShift,
+    },
+    params::Params,
+    Solution,
+};
+
+#[test]
+fn outcomes_are_antisymmetric() {
+    for rules in [Rules::classic(), Rules::lizard_spock()] {
+        for a in rules.hands() {
+            assert_eq!(rules.outcome(a, a), Outcome::Draw);
+            for b in rules.hands().filter(|b| *b != a) {
+                assert_ne!(rules.outcome(a, b), Outcome::Draw);
+                assert_eq!(rules.outcome(a, b), rules.outcome(b, a).reverse());
+            }
+        }
+    }
+}
+
+#[test]
+fn lizard_spock_shapes_beat_two_others() {
+    let rules = Rules::lizard_spock();
+
+    for a in rules.hands() {
+        let beaten = rules
+            .hands()
+            .filter(|b| rules.outcome(a, *b) == Outcome::Win)
+            .count();
+        assert_eq!(beaten, 2, "{}", rules.shape(a).name);
+    }
+}
+
+#[test]
+fn scores_lizard_spock_guides() {
+    let rules = Rules::lizard_spock();
+    let rounds = day2::parse(&rules, "E V\nD W\nA Y\n").unwrap();
+
+    let decoding = rules.shape_decoding();
+    let scores = rounds
+        .iter()
+        .map(|round| Game::new(&rules, round, &decoding).score(&rules))
+        .collect::<Vec<_>>();
+    // lizard poisons Spock both ways round, paper covers rock
+    assert_eq!(scores, [4 + 6, 5, 2 + 6]);
+
+    let error = day2::parse(&Rules::classic(), "E X\n").unwrap_err();
+    assert_eq!(error.expected, "an opponent's hand A, B or C");
+    let error = day2::parse(&rules, "A Q\n").unwrap_err();
+    assert_eq!(error.expected, "an instruction X, Y, Z, V or W");
+}
+
+#[test]
+fn reads_classic_guides_the_same_way() {
+    let input = include_str!("../data/day2.test");
+    let classic = Day2::parse(input).unwrap();
+    let rules = Rules::lizard_spock();
+
+    let rounds = day2::parse(&rules, input).unwrap();
+    let score = day2::score(&rules, &rounds, &rules.shape_decoding());
+    assert_eq!(score, Day2::part1(&classic, &Params::new(2, &[])));
+}
+
+#[test]
+fn rejects_inconsistent_rules() {
+    let shapes = || {
+        vec![
+            Shape::new("rock", 1, 'A', 'X'),
+            Shape::new("paper", 2, 'B', 'Y'),
+            Shape::new("scissors", 3, 'C', 'Z'),
+        ]
+    };
+    let rules = |beats: &[(&str, &str)]| Rules::new(shapes(), beats, [0, 3, 6], ['X', 'Y', 'Z']);
+
+    assert_eq!(
+        rules(&[("rock", "scissors"), ("paper", "rock")]),
+        Err(RulesError::Undecided("paper".into(), "scissors".into()))
+    );
+    assert_eq!(
+        rules(&[("rock", "paper"), ("paper", "rock")]),
+        Err(RulesError::Contradiction("paper".into(), "rock".into()))
+    );
+    assert_eq!(
+        rules(&[("rock", "well")]),
+        Err(RulesError::UnknownShape("well".into()))
+    );
+    assert_eq!(
+        Rules::new(shapes(), &[], [0, 3, 6], ['X', 'X', 'Z']),
+        Err(RulesError::DuplicateLetter('X'))
+    );
+}
+
+#[test]
+fn enumerates_every_decoding() {
+    let rules = Rules::classic();
+
+    let decodings = Decoding::all(&rules, &['X', 'Y', 'Z']);
+    assert_eq!(decodings.len(), 12);
+    assert!(decodings.contains(&rules.shape_decoding()));
+    assert!(decodings.contains(&rules.outcome_decoding()));
+    assert_eq!(
+        Decoding::all(&Rules::lizard_spock(), &['X', 'Y', 'Z']).len(),
+        60 + 6
+    );
+}
+
+#[test]
+fn reports_the_best_and_worst_decodings() {
+    let rules = Rules::classic();
+    let rounds = Day2::parse(include_str!("../data/day2.test")).unwrap();
+    let report = day2::report(&rules, &rounds, usize::MAX, usize::MAX);
+
+    assert_eq!(report.interpretations.len(), 12);
+    assert_eq!(report.best.decoding, "X=scissors Y=paper Z=rock");
+    assert_eq!(report.best.score, 24);
+    assert_eq!(report.worst.score, report.interpretations[11].score);
+    for interpretation in [&report.best, &report.worst] {
+        let total = interpretation
+            .rounds
+            .iter()
+            .map(|round| round.total)
+            .sum::<u32>();
+        assert_eq!(total, interpretation.score);
+    }
+
+    let scores = report
+        .interpretations
+        .iter()
+        .map(|interpretation| (interpretation.decoding.as_str(), interpretation.score))
+        .collect::<Vec<_>>();
+    assert!(scores.contains(&("X=rock Y=paper Z=scissors", 15)));
+    assert!(scores.contains(&("X=lose Y=draw Z=win", 12)));
+
+    // one row per round of the guide, in its order
+    let breakdown = report
+        .best
+        .rounds
+        .iter()
+        .map(|round| {
+            (
+                round.round,
+                round.columns.as_str(),
+                round.shape,
+                round.outcome,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        breakdown,
+        [(1, "A Y", 2, 6), (2, "B X", 3, 6), (3, "C Z", 1, 6)]
+    );
+
+    let report = day2::report(&rules, &rounds, 2, 1);
+    assert_eq!(report.interpretations.len(), 2);
+    assert_eq!(report.best.rounds.len(), 1);
+}
+
+/// returns the first hands a player plays against another one
+fn hands(
+    rules: &Rules,
+    player: &mut dyn Player,
+    opponent: &mut dyn Player,
+    rounds: usize,
+) -> Vec<String> {
+    (0..rounds)
+        .map(|_| {
+            let (mine, theirs) = (player.play(rules), opponent.play(rules));
+            player.observe(rules, mine, theirs);
+            opponent.observe(rules, theirs, mine);
+            rules.shape(mine).name.clone()
+        })
+        .collect()
+}
+
+#[test]
+fn players_react_to_their_opponent() {
+    let rules = Rules::classic();
+    let [rock, paper, _] = rules.hands().collect::<Vec<_>>()[..] else {
+        panic!("The classic rules have three shapes");
+    };
+
+    let mut counter = FrequencyCounter::default();
+    let played = hands(&rules, &mut counter, &mut Fixed::new(&rules, rock), 3);
+    assert_eq!(played, ["paper", "paper", "paper"]);
+
+    let mut shifter = WinStayLoseShift::default();
+    let played = hands(&rules, &mut shifter, &mut Fixed::new(&rules, paper), 3);
+    assert_eq!(played, ["rock", "scissors", "scissors"]);
+
+    let guide = Day2::parse(include_str!("../data/day2.test")).unwrap();
+    let mut follower = FollowTheGuide::new(&rules, &guide, &rules.outcome_decoding()).unwrap();
+    let played = hands(&rules, &mut follower, &mut Fixed::new(&rules, rock), 4);
+    assert_eq!(played, ["rock", "rock", "rock", "rock"]);
+
+    let mut random = Random::new(7);
+    let first = hands(&rules, &mut random, &mut Fixed::new(&rules, rock), 20);
+    random.reset();
+    assert_eq!(
+        hands(&rules, &mut random, &mut Fixed::new(&rules, rock), 20),
+        first
+    );
+}
+
+#[test]
+fn ranks_the_players_of_a_tournament() {
+    let rules = Rules::classic();
+    let mut players = rules
+        .hands()
+        .map(|hand| Box::new(Fixed::new(&rules, hand)) as Box<dyn Player>)
+        .chain([Box::new(Fixed::new(&rules, rules.hands().next().unwrap())) as Box<dyn Player>])
+        .collect::<Vec<_>>();
+
+    let standings = day2::tournament(&rules, &mut players, 10).standings;
+    let table = standings
+        .iter()
+        .map(|standing| {
+            (
+                standing.rank,
+                standing.player.as_str(),
+                standing.won,
+                standing.drawn,
+                standing.lost,
+                standing.score,
+            )
+        })
+        .collect::<Vec<_>>();
+    // each round scores the shape and the outcome, as in the puzzle
+    assert_eq!(
+        table,
+        [
+            (1, "always paper", 2, 0, 1, 2 * (2 + 6) * 10 + 2 * 10),
+            (2, "always scissors", 1, 0, 2, (3 + 6) * 10 + 2 * 3 * 10),
+            (3, "always rock", 1, 1, 1, (1 + 6) * 10 + (1 + 3) * 10 + 10),
+            (3, "always rock", 1, 1, 1, (1 + 6) * 10 + (1 + 3) * 10 + 10),
+        ]
+    );
+}
@@ -0,0 +1,12 @@
+use aoc::{day7::Day7, params::Params, Solution};
+
+#[test]
+fn deletes_nothing_when_there_is_enough_space() {
+    let directories = Day7::parse("$ cd /\n$ ls\ndir a\n1 b\n").unwrap();
+    let mut params = Params::new(7, Day7::PARAMETERS);
+
+    assert_eq!(Day7::part2(&directories, &params), 0);
+    // not even deleting everything frees enough
+    params.set("total_space", 0).unwrap();
+    assert_eq!(Day7::part2(&directories, &params), 1);
+}
@@ -0,0 +1,71 @@
+mod common;
+
+use std::{env, fs, process};
+
+use aoc::fetch::{self, Config, FetchError, Fetched};
+
+fn config(base_url: &str) -> Config {
+    Config {
+        session: Some("secret".to_string()),
+        base_url: Some(base_url.to_string()),
+    }
+}
+
+#[test]
+fn downloads_input_once() {
+    let (base_url, server) = common::serve(vec![(200, "1000\n2000\n".to_string())]);
+    let path = env::temp_dir().join(format!("aoc-fetch-{}/day1.input", process::id()));
+
+    let config = config(&base_url);
+    assert_eq!(
+        fetch::fetch_input(&config, 1, &path).unwrap(),
+        Fetched::Downloaded
+    );
+    // the server only answers a single request
+    assert_eq!(
+        fetch::fetch_input(&config, 1, &path).unwrap(),
+        Fetched::Cached
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+
+    let requests = server.join().unwrap();
+    assert_eq!(requests[0].request_line, "GET /2022/day/1/input HTTP/1.1");
+    assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[test]
+fn reports_rejected_requests() {
+    let (base_url, server) = common::serve(vec![(400, "Please log in".to_string())]);
+    let path = env::temp_dir().join(format!("aoc-fetch-{}/day2.input", process::id()));
+
+    let error = fetch::fetch_input(&config(&base_url), 2, &path).unwrap_err();
+
+    assert!(matches!(error, FetchError::Http(_)));
+    assert!(!path.exists());
+    server.join().unwrap();
+}
+
+#[test]
+fn needs_a_session() {
+    let config = Config {
+        session: None,
+        base_url: Some("http://127.0.0.1:1".to_string()),
+    };
+    let path = env::temp_dir().join(format!("aoc-fetch-{}/day3.input", process::id()));
+
+    assert!(matches!(
+        fetch::fetch_input(&config, 3, &path),
+        Err(FetchError::NoSession)
+    ));
+
+    let config = Config {
+        session: Some(" \n".to_string()),
+        ..config
+    };
+    assert!(matches!(
+        fetch::fetch_input(&config, 3, &path),
+        Err(FetchError::NoSession)
+    ));
+}
@@ -0,0 +1,149 @@
+use std::cmp::Ordering;
+
+use aoc::{
+    day12::Day12,
+    day13::{self, Day13},
+    day18::Day18,
+    day7::Day7,
+    gen::{self, Rng},
+    params::Params,
+    Solution,
+};
+use proptest::prelude::*;
+
+/// returns the generated input of the day parsed by its solution
+fn parse<S: Solution>(seed: u64, size: usize) -> S::Input {
+    let input = gen::generate(S::DAY, &mut Rng::new(seed), size).unwrap();
+    S::parse(&input).unwrap_or_else(|error| panic!("{}\n{}", error, input))
+}
+
+fn params<S: Solution>() -> Params {
+    Params::new(S::DAY, S::PARAMETERS)
+}
+
+/// returns the answers of both parts of the day to its generated input
+fn solve(day: u8, seed: u64, size: usize) -> [String; 2] {
+    let input = gen::generate(day, &mut Rng::new(seed), size).unwrap();
+    let solver = aoc::solver(day).unwrap();
+    let parsed = solver
+        .parse(&input)
+        .unwrap_or_else(|error| panic!("{}\n{}", error, input));
+
+    [1, 2].map(|part| {
+        solver.solve(
+            parsed.as_ref(),
+            part,
+            &Params::new(day, solver.parameters()),
+        )
+    })
+}
+
+/// the size of the inputs benchmarked by default, smaller without optimizations
+const LARGE: usize = if cfg!(debug_assertions) { 300 } else { 1000 };
+
+#[test]
+#[ignore = "slow, run with --ignored"]
+fn solves_large_generated_inputs() {
+    for day in 1..=18 {
+        let answers = solve(day, 1, LARGE);
+        assert!(
+            answers.iter().all(|answer| !answer.is_empty()),
+            "day {}",
+            day
+        );
+    }
+}
+
+proptest! {
+    // solving is slower than parsing, fewer inputs still reach every day
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn solvers_accept_generated_inputs(day in 1u8..=18, seed: u64, size in 1usize..10) {
+        let answers = solve(day, seed, size);
+
+        prop_assert!(answers.iter().all(|answer| !answer.is_empty()));
+    }
+}
+
+proptest! {
+    #[test]
+    fn parsers_accept_generated_inputs(day in 1u8..=18, seed: u64, size in 1usize..30) {
+        let input = gen::generate(day, &mut Rng::new(seed), size).unwrap();
+
+        let parsed = aoc::solver(day).unwrap().parse(&input);
+        prop_assert!(parsed.is_ok(), "{}\n{}", parsed.err().unwrap(), input);
+    }
+
+    /// a line of a generated input is dropped or cut short, the parser may reject the input
+    /// but should not panic
+    #[test]
+    fn parsers_survive_mutated_inputs(
+        day in 1u8..=18,
+        seed: u64,
+        size in 1usize..10,
+        line: usize,
+        cut: usize,
+        drop: bool,
+    ) {
+        let input = gen::generate(day, &mut Rng::new(seed), size).unwrap();
+        let mut lines = input.lines().collect::<Vec<_>>();
+        let line = line % lines.len();
+        match drop {
+            true => {
+                lines.remove(line);
+            }
+            false => {
+                let cut = (0..=lines[line].len())
+                    .filter(|cut| lines[line].is_char_boundary(*cut))
+                    .nth(cut % (lines[line].len() + 1))
+                    .unwrap_or(0);
+                lines[line] = &lines[line][..cut];
+            }
+        }
+
+        let _ = aoc::solver(day).unwrap().parse(&lines.join("\n"));
+    }
+
+    #[test]
+    fn day7_root_holds_every_directory(seed: u64, size in 1usize..50) {
+        let directories = parse::<Day7>(seed, size);
+
+        prop_assert_eq!(directories.len(), size);
+        prop_assert!(directories.values().all(|total| *total <= directories[""]));
+    }
+
+    #[test]
+    fn day12_any_lowest_point_is_as_close_as_the_start(seed: u64, size in 14usize..30) {
+        let map = parse::<Day12>(seed, size);
+
+        prop_assert!(Day12::part2(&map, &params::<Day12>()) <= Day12::part1(&map, &params::<Day12>()));
+    }
+
+    #[test]
+    fn day13_packets_are_totally_ordered(seed: u64, size in 1usize..10) {
+        let packets = parse::<Day13>(seed, size)
+            .into_iter()
+            .flat_map(|(a, b)| [a, b])
+            .collect::<Vec<_>>();
+
+        for a in &packets {
+            prop_assert_eq!(day13::compare(a, a), Ordering::Equal);
+            for b in &packets {
+                prop_assert_eq!(day13::compare(a, b), day13::compare(b, a).reverse());
+                for c in &packets {
+                    if day13::compare(a, b) != Ordering::Greater && day13::compare(b, c) != Ordering::Greater {
+                        prop_assert_ne!(day13::compare(a, c), Ordering::Greater);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn day18_exterior_is_part_of_the_surface(seed: u64, size in 1usize..100) {
+        let cubes = parse::<Day18>(seed, size);
+
+        prop_assert!(Day18::part2(&cubes, &params::<Day18>()) <= Day18::part1(&cubes, &params::<Day18>()));
+    }
+}
@@ -0,0 +1,181 @@
+use aoc::{day6::Day6, params::Params, Maybe, ParseError, Solution};
+
+/// parses a malformed input and returns the error of the solver
+fn parse_error(day: u8, input: &str) -> ParseError {
+    let solver = aoc::solver(day).unwrap_or_else(|| panic!("Day {} has no solver", day));
+
+    match solver.parse(input) {
+        Ok(_) => panic!("Day {} accepted a malformed input", day),
+        Err(error) => error,
+    }
+}
+
+#[test]
+fn day2_unknown_instruction() {
+    let error = parse_error(2, "A Y\nA Q\n");
+
+    assert_eq!((error.day, error.line, error.column), (2, 2, 3));
+    assert_eq!(error.text, "Q");
+}
+
+#[test]
+fn day3_blank_rucksack_or_incomplete_group() {
+    let error = parse_error(3, "aa\n\nbb\n");
+    assert_eq!(
+        (error.line, error.expected.as_str()),
+        (2, "a rucksack with items")
+    );
+
+    let error = parse_error(3, "aa\naa\naa\nbb\n");
+    assert_eq!((error.line, error.text.as_str()), (4, "bb"));
+    assert_eq!(
+        parse_error(3, "ab\n").expected,
+        "an item in both compartments"
+    );
+    assert_eq!(parse_error(3, "aa\nbb\ncc\n").line, 3);
+}
+
+#[test]
+fn day4_section_past_the_last() {
+    let error = parse_error(4, "1-2,3-4294967295\n");
+
+    assert_eq!((error.line, error.column), (1, 7));
+    assert_eq!(error.text, "4294967295");
+}
+
+#[test]
+fn day5_move_without_amount() {
+    let error = parse_error(5, "    [D]\n[N] [C]\n 1   2 \n\nmove x from 1 to 2\n");
+
+    assert_eq!((error.line, error.column), (5, 6));
+    assert_eq!(error.text, "x");
+}
+
+#[test]
+fn day7_directory_listed_but_never_entered_is_empty() {
+    let solver = aoc::solver(7).unwrap();
+
+    assert!(solver.parse("$ cd /\n$ ls\ndir a\n1 b\n").is_ok());
+}
+
+#[test]
+fn day7_file_size_overflow() {
+    let error = parse_error(7, "$ cd /\n$ ls\n99999999999999999999999 b\n");
+    assert_eq!((error.line, error.column), (3, 1));
+    assert_eq!(error.expected, "a file size");
+
+    let error = parse_error(7, "$ cd /\n$ ls\n18446744073709551615 b\n1 c\n");
+    assert_eq!((error.line, error.text.as_str()), (4, "1 c"));
+}
+
+#[test]
+fn day7_malformed_transcript() {
+    assert_eq!(parse_error(7, "$ ls\n1 b\n").line, 1);
+    assert_eq!(parse_error(7, "$ cd /\n$ cd ..\n").column, 6);
+    assert_eq!(parse_error(7, "$ cd /\n$ rm b\n").text, "$ rm b");
+    assert_eq!(parse_error(7, "$ cd /\n$ ls\ndir \n").line, 3);
+    assert_eq!(
+        parse_error(7, "").expected,
+        "the transcript to start with `$ cd /`"
+    );
+}
+
+#[test]
+fn day6_stream_without_marker_has_no_answer() {
+    let params = Params::new(6, Day6::PARAMETERS);
+
+    for input in ["x", "1\n\n\n", "abcabcabcabcabcabc"] {
+        let stream = Day6::parse(input).unwrap();
+        assert_eq!(Day6::part2(&stream, &params), Maybe::Missing("no marker"));
+    }
+    assert_eq!(
+        Day6::part1(&Day6::parse("abcd").unwrap(), &params),
+        Maybe::Found(4)
+    );
+}
+
+#[test]
+fn day7_entry_outside_listing() {
+    let error = parse_error(7, "$ cd /\n1 b\n");
+    assert_eq!((error.line, error.text.as_str()), (2, "1 b"));
+
+    assert_eq!(parse_error(7, "$ cd /\n$ ls\n$ cd /\ndir a\n").line, 4);
+}
+
+#[test]
+fn day11_throw_to_unknown_monkey() {
+    let input = "Monkey 0:
+  Starting items: 79
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 0
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65
+  Operation: new = old + 6
+
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+";
+    let error = parse_error(11, input);
+    assert_eq!((error.line, error.column), (6, 31));
+    assert_eq!(error.text, "3");
+
+    // the blank line within the second monkey shifts its lines, which are reported as they are
+    let error = parse_error(11, &input.replace("monkey 3", "monkey 1"));
+    assert_eq!((error.line, error.column), (13, 30));
+}
+
+#[test]
+fn day11_fewer_than_two_monkeys() {
+    let monkey = "Monkey 0:
+  Starting items: 79
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 0
+    If false: throw to monkey 0
+";
+
+    assert_eq!(parse_error(11, "").expected, "at least two monkeys");
+    assert_eq!(parse_error(11, monkey).expected, "at least two monkeys");
+}
+
+#[test]
+fn day13_invalid_packet() {
+    let error = parse_error(13, "[1,2]\n[1,x]\n");
+
+    assert_eq!((error.line, error.column), (2, 4));
+}
+
+#[test]
+fn day15_without_sensors() {
+    assert_eq!(parse_error(15, "").expected, "at least one sensor");
+}
+
+#[test]
+fn day16_more_flowing_valves_than_bits() {
+    let names = (0..64)
+        .map(|index| format!("V{}", index))
+        .collect::<Vec<_>>();
+    let mut input = String::from("Valve AA has flow rate=0; tunnels lead to valves V0\n");
+    for name in &names {
+        input += &format!("Valve {} has flow rate=1; tunnel leads to valve AA\n", name);
+    }
+
+    let error = parse_error(16, &input);
+    assert_eq!((error.line, error.column), (65, 25));
+    assert_eq!(error.expected, "at most 63 valves with a flow rate");
+}
+
+#[test]
+fn day18_cube_without_depth() {
+    let error = parse_error(18, "1,1,1\n1,2\n");
+
+    assert_eq!((error.line, error.column), (2, 1));
+    assert_eq!(
+        error.to_string(),
+        "day 18, line 2, column 1: expected a cube like `2,2,2`, found \"1,2\""
+    );
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use aoc::{
+    fetch::{self, Config, FetchError},
+    ledger::Status,
+};
+
+fn config(base_url: &str) -> Config {
+    Config {
+        session: Some("secret".to_string()),
+        base_url: Some(base_url.to_string()),
+    }
+}
+
+fn page(message: &str) -> String {
+    format!(
+        "<html><main><article><p>{}</p></article></main></html>",
+        message
+    )
+}
+
+#[test]
+fn submits_the_answer_of_a_part() {
+    let (base_url, server) = common::serve(vec![(
+        200,
+        page("That's the right answer! You are one gold star closer."),
+    )]);
+
+    let status = fetch::submit_answer(&config(&base_url), 4, 2, "845").unwrap();
+
+    assert_eq!(status, Status::Accepted);
+    let requests = server.join().unwrap();
+    assert_eq!(requests[0].request_line, "POST /2022/day/4/answer HTTP/1.1");
+    assert_eq!(requests[0].header("Cookie"), Some("session=secret"));
+    assert_eq!(requests[0].body, "level=2&answer=845");
+}
+
+#[test]
+fn reads_the_verdict() {
+    let replies = [
+        "That's not the right answer; your answer is too high.",
+        "That's not the right answer; your answer is too low.",
+        "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+    ];
+    let (base_url, server) =
+        common::serve(replies.iter().map(|reply| (200, page(reply))).collect());
+
+    let statuses = (0..replies.len())
+        .map(|_| fetch::submit_answer(&config(&base_url), 1, 1, "1").unwrap())
+        .collect::<Vec<Status>>();
+
+    assert_eq!(statuses, [Status::TooHigh, Status::TooLow, Status::Wrong]);
+    server.join().unwrap();
+}
+
+#[test]
+fn reports_unjudged_submissions() {
+    let (base_url, server) = common::serve(vec![(
+        200,
+        page("You gave an answer too recently; you have to wait after submitting an answer before trying again."),
+    )]);
+
+    let error = fetch::submit_answer(&config(&base_url), 1, 1, "1").unwrap_err();
+
+    assert!(matches!(error, FetchError::Submission(_)));
+    server.join().unwrap();
+}